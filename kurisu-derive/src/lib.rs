@@ -52,10 +52,22 @@
 //! usize         | Defines value type of an Option or Argument
 //! isize         | Defines value type of an Option or Argument
 //! f64           | Defines value type of an Option or Argument
-//! Vec<T>        | Defines a repetitive Option or Argument
+//! Vec<T>        | Defines a repetitive Option or Argument, so do `VecDeque<T>`, `LinkedList<T>` & `BinaryHeap<T>`
+//! HashSet<T>    | Defines a repetitive Option or Argument keeping a single one of duplicated values, so does `BTreeSet<T>`
+//! [T; N]        | Defines a repetitive Option or Argument of exactly N values
+//! (A, B)        | Defines an Option taking one value per element, example: `--size 800 600` with `num_values = 2`, or a single value split on `,` or on `x` between numbers, example: `--size 800x600`
+//!
+//! Arrays and the collections above take any number of values, so does any type whose `Parser` sets `MULTIPLE`,
+//! whatever its name or alias. Any other type takes a single one.
 //!
 //! ## Override default behaviors on main Struct
 //!
+//! Every annotation is validated at compile time, an unknown annotation or a value of the wrong
+//! kind, example: `pos = "1"` instead of `pos = 1`, is reported as a compile error. So are duplicated
+//! or missing positions and `required_if` referencing an unknown field. What can only be verified at
+//! runtime, such as a `default` value parsing into its field type, is reported by
+//! **_[Kurisu::assert_schema](../kurisu/trait.Kurisu.html#method.assert_schema)_** from within a test.
+//!
 //! The struct that drives these behaviors is handled by
 //! *__[kurisu::Info](../kurisu/struct.Info.html)__*, most of the annotation are associated to one
 //! of its field.
@@ -67,6 +79,12 @@
 //! desc          | None         | desc = "some short text"| Change description on usage screen
 //! doc           | None         | ///                     | Change DISCUSSION text on usage screen
 //! allow_noargs  | false        | allow_noargs            | Does not display usage screen if no command line values
+//! allow_unknown | false        | allow_unknown           | Unknown flags & options are ignored instead of being a usage error
+//! allow_abbrev  | false        | allow_abbrev            | Accepts any unambiguous prefix of a long flag / option, example: `--col` for `--color`
+//! env_prefix    | None         | env_prefix = "MYAPP_"   | Every field looks up the environment variable of its name in SCREAMING_SNAKE_CASE prefixed by this one, example: `MYAPP_MAX_COUNT`, unless it has its own `env`, `env_prefix` or `noenv`, or it is `trailing` or `unknown`. Fields of a flattened struct use that struct's own `env_prefix` instead. They are listed in the ENVIRONMENT section of the usage screen
+//! implicit_env  | false        | implicit_env            | Migration switch to the behavior prior to `env` being opt-in: every field without `env` or `env_prefix` looks up any environment variable matching its name case-insensitively, example: `path` reads `PATH`, and a `default` value applies before the environment variable
+//! precedence    | "cli,env,default" | precedence = "cli,default,env" | Order in which a value is looked up, by default the command line, then the environment variable, then the `default` annotation
+//! allow_hyphen_values | false  | allow_hyphen_values     | Every option takes the next command line value as its value even if it starts with a hyphen, except `--`, example: `--pattern -foo`
 //! &nbsp;        | &nbsp;       | cargo                   | Will try to fetch name, version & desc from the Cargo.toml, the specific field annotation take precedence on the cargo annotation
 //! &nbsp;        | &nbsp;       | nosort                  | Avoid sorting alphabetically arguments, flags & options on usage screen
 //! &nbsp;        | &nbsp;       | auto_shorts             | Enables the auto generation of short flags / options according to their field's name first letter, by default no short flags / options are generated unless specified on the struct's field annotation
@@ -92,16 +110,29 @@
 //! Field name    | Default       | Annotation                 | Description
 //! --------------|---------------|----------------------------|---------------------------------------------
 //! vname         | field's name  | vname = "myname"           | Change the name of the <VALUE> in the usage screen
-//! position      | None          | pos **OR** pos = "1"       | Defines the position of an argument, `pos` without a value defines an infinite positional argument
+//! position      | None          | pos **OR** pos = 1 **OR** pos = "last" | Defines the position of an argument, `pos` without a value defines an infinite positional argument, `pos = "last"` always takes the last positional argument, example: `cp SOURCES... DEST`
+//! trailing      | false         | trailing                   | Collects every raw command line value after `--`, its type is `Vec<String>`
+//! unknown       | false         | unknown                    | Collects every unknown flag & option as typed, example: `--other=value`, its type is `Vec<String>`
+//! numeric_shorthand | false     | numeric_shorthand          | Assigns `-NUM` to this integer option, which needs a short or long flag, example: `-5` for `--context=5`
 //! doc           | None          | ///                        | Defines the description of arg on the usage screen
 //! short         | None          | short **OR** short = "b"   | Change the letter used by short flag / option, otherwise it takes the first letter of the struct's field name
 //! long          | field's name  | nolong **OR** long = "myname" | Change or remove the long flag / option
-//! env           | field's name  | env = "MYSQL_HOST"         | By default the field's name is used to look for environment variable match as last resort, this is to change which environment variable is being matched
-//! env_prefix    | None          | env_prefix = "MYSQL_"      | Will take the field's name and prefix it and look for a match environment variable
+//! env           | None          | env = "MYSQL_HOST" **OR** noenv | Environment variable looked up, with the exact same case, when missing from the command line, several names are tried in order, example: `env = "DATABASE_URL,DB_URL"`. `noenv` opts out of the struct's `env_prefix`
+//! env_delimiter | ":" for collections of `PathBuf`, otherwise `delimiter` | env_delimiter = ";" | Splits the environment variable of a `Vec` into multiple values, example: `MYAPP_INCLUDE=a:b:c`
+//! env_prefix    | struct's env_prefix | env_prefix = "MYSQL_" | Will take the field's name in SCREAMING_SNAKE_CASE and prefix it and look for a match environment variable, example: `MYSQL_MAX_COUNT`
 //! required_if   | None          | required_if = "fieldname"  | Will make this option required if the other flag/option is present
 //! default       | ""            | default = "42"             | If not present in the command line values, struct field will be assigned this default value instead of default type value
+//! default_missing | None        | default_missing = "always" | Makes the value optional, the option given without a value is assigned this one, example: `--color` **OR** `--color=never`
+//! allow_hyphen_values | false   | allow_hyphen_values        | The option takes the next command line value as its value even if it starts with a hyphen, except `--`, example: `--args -Xmx2g`
+//! delimiter     | ","           | delimiter = ":" **OR** no_delimiter | Change or remove the delimiter splitting a value into multiple ones for a `Vec`, it can be escaped with a backslash, example: `--exclude 'a\,b.txt,c.txt'`
+//! repeat        | "last", "error" for `pos`, `trailing` & `unknown` | repeat = "first" **OR** repeat = "last" **OR** repeat = "error" | Which value is kept when an argument taking a single value is given more than once, `error` makes it a usage error, example: `--color=auto --color=never`. Values are never joined, use a `Vec<String>` to collect them
+//! num_values    | 1             | num_values = 2             | Number of values following each occurrence of the option, example: `--rename OLD NEW` into a tuple or a `Vec`, each value can be named with `vname = "old,new"`
 //! exit          | None          | exit = "my_exit_func"      | Local function that triggers std::process::exit() after being executed. A bit like the usage display, it will stop execution at `from_args`
-//! &nbsp;        | &nbsp;        | parse_with = "my_func"     | Local function that allows custom parsing for this argument/flag/option
+//! &nbsp;        | &nbsp;        | parse_with = "my_func"     | Local function parsing each value, `fn(&str) -> Result<T, E>`, for a field of type `T`, which must implement `Default` for a missing value, or a collection of `T` such as `Vec<T>`, `HashSet<T>` or `[T; N]`. A value it rejects is a usage error naming the option, it is called once when parsing and once again by `validate_usage`. For advanced use `fn(name: &str, info: &Info) -> T` is given the whole parsed `Info` instead
+//! &nbsp;        | &nbsp;        | from_str                   | Parses the value with the `FromStr` implementation of a type that has no `Parser` one, example: `level: LogLevel`, the type must also implement `Default` which is used for a missing value. A value `FromStr` rejects is a usage error naming the option. Cannot be used with `parse_with`, `flatten` or on a skipped field
+//! &nbsp;        | &nbsp;        | flatten                    | Splices the arguments of another `derive(Kurisu)` struct into this one
+//! &nbsp;        | &nbsp;        | skip                       | Not an argument, the field is set to its `Default`, to its `default` value parsed like the one of an argument, or to `default_expr = "Rust expression"`. Fields prefixed by `_` are always skipped
+//! &nbsp;        | &nbsp;        | skip_with = "my_func"      | Not an argument, the field is set by calling `my_func()`
 //!
 //! Example:
 //! ```ignore
@@ -119,6 +150,10 @@
 //!     more: bool,
 //!     /// description for `crashed` on usage screen
 //!     crashed: bool,
+//!     #[kurisu(flatten)] // Shared options, DbArgs must also derive Kurisu
+//!     db: DbArgs,
+//!     #[kurisu(skip, default = "/etc/yargs")]
+//!     config_dir: PathBuf,
 //! }
 //! ```
 //!
@@ -127,6 +162,8 @@
 
 extern crate proc_macro;
 
+mod validate;
+
 use proc_macro::TokenStream;
 use proc_macro_error::abort_call_site;
 use quote::{quote, quote_spanned};
@@ -211,6 +248,22 @@ fn meta_value(name: &str, attrs: &[(proc_macro2::Ident, Option<syn::Lit>)], opti
     })
 }
 
+fn meta_expr(name: &str, attrs: &[(proc_macro2::Ident, Option<syn::Lit>)]) -> Option<proc_macro2::TokenStream> {
+    attrs.iter().find_map(|(ident, value)| match value {
+        Some(syn::Lit::Str(lit)) if ident.eq(name) => match lit.parse::<syn::Expr>() {
+            Ok(expr) => Some(quote_spanned! (lit.span() => #expr)),
+            Err(error) => Some(error.to_compile_error()),
+        },
+        _ => None,
+    })
+}
+
+/// Fields annotated with `#[kurisu(skip)]` or prefixed by `_` are not command line arguments
+fn is_field_skipped(field: &Field, attrs: &[(proc_macro2::Ident, Option<syn::Lit>)]) -> bool {
+    let prefixed = field.ident.clone().unwrap().to_string().starts_with('_');
+    prefixed || meta_value("skip", attrs, false).is_some() || meta_value("skip_with", attrs, false).is_some()
+}
+
 fn sanitize_option_short(
     short: proc_macro2::TokenStream,
     field: &Field,
@@ -270,12 +323,44 @@ fn sanitize_option_long(long: proc_macro2::TokenStream, field: &Field, existing_
 }
 
 fn impl_kurisu_macro(ast: &syn::DeriveInput) -> TokenStream {
+    let mut errors: Vec<syn::Error> = Vec::new();
+    validate::attributes(&ast.attrs, validate::STRUCT_ATTRIBUTES, &mut errors);
+    for field in get_fields_named(&ast.data).named.iter() {
+        validate::attributes(&field.attrs, validate::FIELD_ATTRIBUTES, &mut errors);
+    }
+
+    // Layout & references are only checked on well formed annotations
+    if errors.is_empty() {
+        validate::positions(get_fields_named(&ast.data), &mut errors);
+        validate::references(get_fields_named(&ast.data), &mut errors);
+        let auto_shorts = meta_attributes(&ast.attrs).iter().any(|(ident, _)| ident == "auto_shorts");
+        validate::single_fields(get_fields_named(&ast.data), auto_shorts, &mut errors);
+        validate::combinations(get_fields_named(&ast.data), &mut errors);
+        validate::num_values(get_fields_named(&ast.data), &mut errors);
+    }
+
+    if let Some(error) = errors.into_iter().reduce(|mut a, b| {
+        a.combine(b);
+        a
+    }) {
+        return error.to_compile_error().into();
+    }
+
     let name = &ast.ident;
     let struct_meta_attrs = meta_attributes(&ast.attrs);
     let script_doc = meta_value("doc", &struct_meta_attrs, true).unwrap_or(quote! {None});
     let script_nosort = meta_value("nosort", &struct_meta_attrs, true).is_some();
     let script_noargs = meta_value("allow_noargs", &struct_meta_attrs, true).is_some();
+    let script_unknown = meta_value("allow_unknown", &struct_meta_attrs, true).is_some();
+    let script_abbrev = meta_value("allow_abbrev", &struct_meta_attrs, true).is_some();
     let auto_shorts = meta_value("auto_shorts", &struct_meta_attrs, true).is_some();
+    let script_hyphen_values = meta_value("allow_hyphen_values", &struct_meta_attrs, true).is_some();
+    let script_env_prefix = meta_value("env_prefix", &struct_meta_attrs, true).unwrap_or(quote! {None});
+    let script_implicit_env = meta_value("implicit_env", &struct_meta_attrs, false).is_some();
+    let script_precedence = match struct_meta_attrs.iter().find(|(ident, _)| ident == "precedence") {
+        Some((_, Some(syn::Lit::Str(lit)))) if lit.value() == "cli,default,env" => quote! {::kurisu::arg::Precedence::CliDefaultEnv},
+        _ => quote! {::kurisu::arg::Precedence::CliEnvDefault},
+    };
     let mut script_desc = meta_value("desc", &struct_meta_attrs, true).unwrap_or(quote! {None});
     let mut script_version = meta_value("version", &struct_meta_attrs, true).unwrap_or(quote! {None});
     let mut script_name = meta_value("name", &struct_meta_attrs, true).unwrap_or(quote! {None});
@@ -314,17 +399,60 @@ fn impl_kurisu_macro(ast: &syn::DeriveInput) -> TokenStream {
         let ty = &f.ty;
 
         let field_meta_attrs = meta_attributes(&f.attrs);
+        if is_field_skipped(f, &field_meta_attrs) {
+            return quote! {};
+        }
+
+        if meta_value("flatten", &field_meta_attrs, false).is_some() {
+            return quote_spanned! (ty.span() => kurisu_args.extend(<#ty as ::kurisu::Kurisu>::get_args()););
+        }
+
         let value_name = meta_value("vname", &field_meta_attrs, true).unwrap_or(quote! {None});
         let field_doc = meta_value("doc", &field_meta_attrs, true).unwrap_or(quote! {None});
         let field_default = meta_value("default", &field_meta_attrs, false).unwrap_or(quote! {""});
+        let default_missing = meta_value("default_missing", &field_meta_attrs, true).unwrap_or(quote! {None});
+        let num_values_type = meta_value("parse_with", &field_meta_attrs, false).is_none()
+            && meta_value("from_str", &field_meta_attrs, false).is_none()
+            && meta_value("num_values", &field_meta_attrs, false).is_some();
+        let num_values = meta_value("num_values", &field_meta_attrs, true).unwrap_or(quote! {None});
+        let allow_hyphen_values = script_hyphen_values || meta_value("allow_hyphen_values", &field_meta_attrs, false).is_some();
+        let delimiter = meta_value("delimiter", &field_meta_attrs, true).unwrap_or(quote! {None});
+        let no_delimiter = meta_value("no_delimiter", &field_meta_attrs, false).is_some();
         let required_if = meta_value("required_if", &field_meta_attrs, true).unwrap_or(quote! {None});
-        let env_prefix = meta_value("env_prefix", &field_meta_attrs, true).unwrap_or(quote! {None});
-        let env = meta_value("env", &field_meta_attrs, true).unwrap_or(quote! {None});
+        let mut env_prefix = meta_value("env_prefix", &field_meta_attrs, true).unwrap_or_else(|| script_env_prefix.clone());
+        let mut env = meta_value("env", &field_meta_attrs, true).unwrap_or(quote! {None});
+        let env_delimiter = match meta_value("env_delimiter", &field_meta_attrs, true) {
+            Some(env_delimiter) => env_delimiter,
+            // Fields parsed by other means than `Parser` split on the command line delimiter
+            None if meta_value("parse_with", &field_meta_attrs, false).is_some() => quote! {None},
+            None if meta_value("from_str", &field_meta_attrs, false).is_some() => quote! {None},
+            None => quote_spanned! (ty.span() => <#ty as ::kurisu::arg::Parser>::ENV_DELIMITER),
+        };
+        let trailing = meta_value("trailing", &field_meta_attrs, false).is_some();
+        let unknown = meta_value("unknown", &field_meta_attrs, false).is_some();
+        let infinite = meta_value("pos", &field_meta_attrs, false).filter(|p| p.is_empty()).is_some();
+        let repeat = match field_meta_attrs.iter().find(|(ident, _)| ident == "repeat") {
+            Some((_, Some(syn::Lit::Str(lit)))) if lit.value() == "first" => quote! {::kurisu::arg::Repeat::First},
+            Some((_, Some(syn::Lit::Str(lit)))) if lit.value() == "last" => quote! {::kurisu::arg::Repeat::Last},
+            Some((_, Some(syn::Lit::Str(lit)))) if lit.value() == "error" => quote! {::kurisu::arg::Repeat::Error},
+            // A single value field taking any number of values never drops them silently
+            _ if infinite || trailing || unknown => quote! {::kurisu::arg::Repeat::Error},
+            _ => quote! {::kurisu::arg::Repeat::Last},
+        };
+        // Trailing & unknown arguments only ever take their values from the command line
+        if meta_value("noenv", &field_meta_attrs, false).is_some() || trailing || unknown {
+            env_prefix = quote! {None};
+            env = quote! {None};
+        }
         let mut field_short = meta_value("short", &field_meta_attrs, true).unwrap_or(quote! {None});
         let mut field_long = meta_value("long", &field_meta_attrs, true).unwrap_or_default();
         let field_input = meta_value("pos", &field_meta_attrs, false);
+        let numeric_shorthand = meta_value("numeric_shorthand", &field_meta_attrs, false).is_some();
         let mut input_position = quote! {None};
-        if let Some(position) = field_input {
+        if trailing || unknown {
+            field_short = quote! {None};
+            field_long = quote! {None};
+        } else if let Some(position) = field_input {
             field_short = quote! {None};
             field_long = quote! {None};
 
@@ -336,6 +464,8 @@ fn impl_kurisu_macro(ast: &syn::DeriveInput) -> TokenStream {
 
                 has_pos_infinite = true;
                 input_position = quote! {Some (0)};
+            } else if position.to_string() == "\"last\"" {
+                input_position = quote_spanned! (position.span() => Some (-1));
             } else {
                 input_position = quote_spanned! (position.span() => Some (#position));
             }
@@ -367,11 +497,28 @@ fn impl_kurisu_macro(ast: &syn::DeriveInput) -> TokenStream {
             .map(|a| a.replace('"', ""))
             .collect::<Vec<String>>();
 
-        quote_spanned! (name.span() => {
-            ::kurisu::Arg {
+        let multiple = if let Some(cb) = meta_value("parse_with", &field_meta_attrs, false) {
+            let ident = syn::Ident::new(cb.to_string().trim_matches('"'), cb.span());
+            quote_spanned! (ty.span() => ::kurisu::arg::is_multiple_with::<_, #ty, _>(&#ident))
+        } else if meta_value("from_str", &field_meta_attrs, false).is_some() {
+            quote! {false}
+        } else {
+            quote_spanned! (ty.span() => <#ty as ::kurisu::arg::Parser>::MULTIPLE)
+        };
+
+        // A type taking any number of values can only be told apart by its `Parser`
+        let num_values_check = match (&num_values_type, ty) {
+            (true, syn::Type::Tuple(_)) | (false, _) => quote! {},
+            (true, _) => quote_spanned! (ty.span() =>
+                const _: () = assert!(<#ty as ::kurisu::arg::Parser>::MULTIPLE, "`num_values` requires a tuple, an array or a collection such as `Vec`");
+            ),
+        };
+
+        quote_spanned! (name.span() => #num_values_check kurisu_args.push(::kurisu::Arg {
                 name: stringify!(#name),
                 vname: #value_name,
                 value_type: stringify!(#ty),
+                multiple: #multiple,
                 position: #input_position,
                 short: #field_short,
                 long: #field_long,
@@ -381,25 +528,139 @@ fn impl_kurisu_macro(ast: &syn::DeriveInput) -> TokenStream {
                 env: #env,
                 env_prefix: #env_prefix,
                 required_if: #required_if,
+                trailing: #trailing,
+                unknown: #unknown,
+                numeric_shorthand: #numeric_shorthand,
                 default: #field_default,
+                default_missing: #default_missing,
+                num_values: #num_values,
+                allow_hyphen_values: #allow_hyphen_values,
+                delimiter: #delimiter,
+                no_delimiter: #no_delimiter,
+                env_delimiter: #env_delimiter,
+                repeat: #repeat,
                 value: Vec::new(),
                 occurrences: 0,
-            }
-        })
+            });
+        )
     });
 
-    // TODO: Implement the possibility to skip a struct field by prefixing it with _, Should not generate an Arg and should set default value to struct
     let struct_values = fields.named.iter().map(|f| {
         let name = &f.ident.clone().unwrap();
+        let ty = &f.ty;
 
         let field_meta_attrs = meta_attributes(&f.attrs);
         let field_parser = meta_value("parse_with", &field_meta_attrs, false);
-        if let Some(cb) = field_parser {
+        if is_field_skipped(f, &field_meta_attrs) {
+            let skip_with = meta_value("skip_with", &field_meta_attrs, false);
+            if let Some(cb) = skip_with {
+                let func_name = cb.to_string();
+                let ident = syn::Ident::new(func_name.trim_matches('"'), cb.span());
+                quote_spanned! (name.span() => #name: #ident(),)
+            } else if let Some(default) = meta_expr("default_expr", &field_meta_attrs) {
+                quote_spanned! (name.span() => #name: #default,)
+            } else if let Some(default) = meta_value("default", &field_meta_attrs, false) {
+                let multiple = quote_spanned! (ty.span() => <#ty as ::kurisu::arg::Parser>::MULTIPLE);
+                quote_spanned! (name.span() =>
+                    #name: {
+                        let arg = ::kurisu::Arg { value_type: stringify!(#ty), multiple: #multiple, ..Default::default() };
+                        <#ty as ::kurisu::arg::Parser>::parse(&arg.split_value(#default))
+                    },
+                )
+            } else {
+                quote_spanned! (name.span() => #name: Default::default(),)
+            }
+        } else if meta_value("flatten", &field_meta_attrs, false).is_some() {
+            quote_spanned! (name.span() => #name: <#ty as ::kurisu::Kurisu>::from_info(info),)
+        } else if meta_value("from_str", &field_meta_attrs, false).is_some() {
+            quote_spanned! (name.span() => #name: ::kurisu::parse_from_str(stringify!(#name), info),)
+        } else if let Some(cb) = field_parser {
             let func_name = cb.to_string();
             let ident = syn::Ident::new(func_name.trim_matches('"'), cb.span());
-            quote_spanned! (name.span() => #name: #ident(stringify!(#name), &info),)
+            quote_spanned! (name.span() => #name: <_ as ::kurisu::arg::ParseWith<_, #ty>>::parse_with(&#ident, stringify!(#name), info),)
         } else {
-            quote_spanned! (name.span() => #name: ::kurisu::parse_value(stringify!(#name), &info),)
+            quote_spanned! (name.span() => #name: ::kurisu::parse_value(stringify!(#name), info),)
+        }
+    });
+
+    let default_errors = fields.named.iter().map(|f| {
+        let name = &f.ident.clone().unwrap();
+        let ty = &f.ty;
+
+        let field_meta_attrs = meta_attributes(&f.attrs);
+        if is_field_skipped(f, &field_meta_attrs) {
+            let multiple = quote_spanned! (ty.span() => <#ty as ::kurisu::arg::Parser>::MULTIPLE);
+            match meta_value("default", &field_meta_attrs, false) {
+                Some(default) if meta_value("skip_with", &field_meta_attrs, false).is_none() => quote_spanned! (name.span() =>
+                    let arg = ::kurisu::Arg { value_type: stringify!(#ty), multiple: #multiple, ..Default::default() };
+                    if let Err(error) = <#ty as ::kurisu::arg::Parser>::validate(&arg.split_value(#default)) {
+                        errors.push(format!("default \"{}\" of `{}` is not a valid {}: {}", #default, stringify!(#name), stringify!(#ty), error));
+                    }
+                ),
+                _ => quote! {},
+            }
+        } else if meta_value("parse_with", &field_meta_attrs, false).is_some() {
+            quote! {}
+        } else if meta_value("flatten", &field_meta_attrs, false).is_some() {
+            quote_spanned! (ty.span() => errors.extend(<#ty as ::kurisu::Kurisu>::get_default_errors());)
+        } else {
+            let from_str = meta_value("from_str", &field_meta_attrs, false).is_some();
+            let checks = ["default", "default_missing"].iter().filter_map(|annotation| {
+                let default = meta_value(annotation, &field_meta_attrs, false)?;
+                if from_str {
+                    return Some(quote_spanned! (name.span() =>
+                        if let Err(error) = <#ty as ::std::str::FromStr>::from_str(#default) {
+                            errors.push(format!("{} \"{}\" of `{}` is not a valid {}: {}", #annotation, #default, stringify!(#name), stringify!(#ty), error));
+                        }
+                    ));
+                }
+
+                Some(quote_spanned! (name.span() =>
+                    // Split like it is at runtime, example: `default = "1,2"` for a `Vec`
+                    let values = kurisu_args.iter().find(|a| a.name == stringify!(#name)).map(|a| a.split_value(#default));
+                    if let Err(error) = <#ty as ::kurisu::arg::Parser>::validate(&values.unwrap_or_default()) {
+                        errors.push(format!("{} \"{}\" of `{}` is not a valid {}: {}", #annotation, #default, stringify!(#name), stringify!(#ty), error));
+                    }
+                ))
+            });
+
+            quote! { #(#checks)* }
+        }
+    });
+
+    let value_errors = fields.named.iter().map(|f| {
+        let name = &f.ident.clone().unwrap();
+        let ty = &f.ty;
+
+        let field_meta_attrs = meta_attributes(&f.attrs);
+        if is_field_skipped(f, &field_meta_attrs) {
+            quote! {}
+        } else if meta_value("flatten", &field_meta_attrs, false).is_some() {
+            quote_spanned! (ty.span() =>
+                if let Some(error) = <#ty as ::kurisu::Kurisu>::get_value_error(info) {
+                    return Some(error);
+                }
+            )
+        } else if meta_value("from_str", &field_meta_attrs, false).is_some() {
+            quote_spanned! (name.span() =>
+                if let Some(error) = ::kurisu::validate_from_str::<#ty>(stringify!(#name), info) {
+                    return Some(error);
+                }
+            )
+        } else if let Some(cb) = meta_value("parse_with", &field_meta_attrs, false) {
+            let func_name = cb.to_string();
+            let ident = syn::Ident::new(func_name.trim_matches('"'), cb.span());
+            quote_spanned! (name.span() =>
+                if let Some(error) = <_ as ::kurisu::arg::ParseWith<_, #ty>>::validate_with(&#ident, stringify!(#name), info) {
+                    return Some(error);
+                }
+            )
+        } else {
+            quote_spanned! (ty.span() =>
+                if let Some(error) = ::kurisu::validate_value::<#ty>(stringify!(#name), info) {
+                    return Some(error);
+                }
+            )
         }
     });
 
@@ -408,77 +669,68 @@ fn impl_kurisu_macro(ast: &syn::DeriveInput) -> TokenStream {
             fn from_args(env_args: Vec<String>) -> Self {
                 let info = Self::get_info_instance(env_args).lock().unwrap();
                 ::kurisu::exit_args(&info, |code| { std::process::exit(code); });
+                Self::from_info(&info)
+            }
+
+            fn from_info(info: &::kurisu::Info<'_>) -> Self {
                 #name {
                     #(#struct_values)*
                 }
             }
 
+            fn get_value_error(info: &::kurisu::Info<'static>) -> Option<::kurisu::arg::Error> {
+                #(#value_errors)*
+                None
+            }
+
+            fn get_default_errors() -> Vec<String> {
+                let mut errors: Vec<String> = Vec::new();
+                let kurisu_args = Self::get_args();
+                #(#default_errors)*
+                errors
+            }
+
+            fn get_args() -> Vec<::kurisu::Arg<'static>> {
+                let mut kurisu_args: Vec<::kurisu::Arg<'static>> = Vec::new();
+                #(#args_array)*
+                kurisu_args
+            }
+
             fn get_info_instance(env_args: Vec<String>) -> &'static std::sync::Mutex<::kurisu::Info<'static>> {
+                Self::get_info_instance_env(env_args, None)
+            }
+
+            fn get_info_instance_env(env_args: Vec<String>, env: Option<::kurisu::Env>) -> &'static std::sync::Mutex<::kurisu::Info<'static>> {
                 static INSTANCE: ::kurisu::OnceCell<std::sync::Mutex<::kurisu::Info>> = ::kurisu::OnceCell::new();
                 INSTANCE.get_or_init(move || {
-                    let mut kurisu_args = vec![
-                        ::kurisu::Arg {
-                            name: "usage",
-                            vname: None,
-                            value_type: "bool",
-                            position: None,
-                            short: Some("h"),
-                            long: Some("help"),
-                            aliases: Vec::new(),
-                            doc: Some("Prints this message"),
-                            exit: None,
-                            env: None,
-                            env_prefix: None,
-                            required_if: None,
-                            default: "false",
-                            value: Vec::new(),
-                            occurrences: 0,
-                        },
-                        ::kurisu::Arg {
-                            name: "version",
-                            vname: None,
-                            value_type: "bool",
-                            position: None,
-                            short: Some("V"),
-                            long: Some("version"),
-                            aliases: Vec::new(),
-                            doc: Some("Prints version information"),
-                            exit: None,
-                            env: None,
-                            env_prefix: None,
-                            required_if: None,
-                            default: "false",
-                            value: Vec::new(),
-                            occurrences: 0,
-                        },
-                        #(#args_array),*
-                    ];
-
-                    let mut env_args = ::kurisu::normalize_env_args(&env_args, &kurisu_args);
-                    let positions: Vec<i8> = kurisu_args
-                        .iter()
-                        .filter(|a| a.position.is_some() && a.position.unwrap() != 0)
-                        .map(|a| a.position.unwrap())
-                        .collect();
-
-                    for arg in kurisu_args.iter_mut() {
-                        arg.set_value(&env_args, &positions);
-                    }
+                    let env = env.unwrap_or_else(::kurisu::Env::capture);
+                    let mut kurisu_args = ::kurisu::get_builtin_args();
+                    kurisu_args.extend(Self::get_args());
 
-                    // Sort args for Display usage
-                    if !#script_nosort {
-                        kurisu_args.sort_by(|a, b| a.partial_cmp(b).unwrap());
-                    }
-
-                    std::sync::Mutex::new(Info {
+                    let mut info = Info {
                         name: #script_name,
                         version: #script_version,
                         desc: #script_desc,
                         doc: #script_doc,
                         allow_noargs: #script_noargs,
+                        allow_unknown: #script_unknown,
+                        allow_abbrev: #script_abbrev,
+                        precedence: #script_precedence,
+                        implicit_env: #script_implicit_env,
+                        env,
                         env_args,
                         args: kurisu_args,
-                    })
+                        tokens: Vec::new(),
+                        names: std::collections::HashMap::new(),
+                    };
+
+                    // Sort args for Display usage
+                    if !#script_nosort {
+                        info.args.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                    }
+                    ::kurisu::set_values(&mut info);
+
+                    std::sync::Mutex::new(info)
                 })
             }
         }
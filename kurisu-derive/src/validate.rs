@@ -0,0 +1,362 @@
+use syn::spanned::Spanned;
+use syn::{Attribute, Lit, Meta, NestedMeta};
+
+/// Kind of value an annotation accepts
+#[derive(Clone, Copy, PartialEq)]
+pub enum Value {
+    /// No value, example: `nosort`
+    Flag,
+    /// String literal, example: `name = "mycli"`
+    Str,
+    /// Integer literal, example: `pos = 1`
+    Int,
+    /// String literal of a single character, example: `short = "b"`
+    Char,
+    /// Specific string literal, example: `pos = "last"`
+    Keyword(&'static str),
+}
+
+pub const STRUCT_ATTRIBUTES: &[(&str, &[Value])] = &[
+    ("name", &[Value::Str]),
+    ("version", &[Value::Str]),
+    ("desc", &[Value::Str]),
+    ("doc", &[Value::Str]),
+    ("allow_noargs", &[Value::Flag]),
+    ("cargo", &[Value::Flag]),
+    ("nosort", &[Value::Flag]),
+    ("auto_shorts", &[Value::Flag]),
+    ("allow_unknown", &[Value::Flag]),
+    ("allow_abbrev", &[Value::Flag]),
+    ("allow_hyphen_values", &[Value::Flag]),
+    ("env_prefix", &[Value::Str]),
+    ("implicit_env", &[Value::Flag]),
+    ("precedence", &[Value::Keyword("cli,env,default"), Value::Keyword("cli,default,env")]),
+];
+
+pub const FIELD_ATTRIBUTES: &[(&str, &[Value])] = &[
+    ("vname", &[Value::Str]),
+    ("pos", &[Value::Flag, Value::Int, Value::Keyword("last")]),
+    ("doc", &[Value::Str]),
+    ("short", &[Value::Flag, Value::Char]),
+    ("long", &[Value::Flag, Value::Str]),
+    ("nolong", &[Value::Flag]),
+    ("aliases", &[Value::Str]),
+    ("env", &[Value::Str]),
+    ("env_prefix", &[Value::Str]),
+    ("noenv", &[Value::Flag]),
+    ("env_delimiter", &[Value::Char]),
+    ("required_if", &[Value::Str]),
+    ("default", &[Value::Str]),
+    ("default_expr", &[Value::Str]),
+    ("default_missing", &[Value::Str]),
+    ("num_values", &[Value::Int]),
+    ("allow_hyphen_values", &[Value::Flag]),
+    ("delimiter", &[Value::Char]),
+    ("no_delimiter", &[Value::Flag]),
+    ("repeat", &[Value::Keyword("last"), Value::Keyword("first"), Value::Keyword("error")]),
+    ("exit", &[Value::Str]),
+    ("parse_with", &[Value::Str]),
+    ("from_str", &[Value::Flag]),
+    ("flatten", &[Value::Flag]),
+    ("skip", &[Value::Flag]),
+    ("skip_with", &[Value::Str]),
+    ("trailing", &[Value::Flag]),
+    ("unknown", &[Value::Flag]),
+    ("numeric_shorthand", &[Value::Flag]),
+];
+
+/// Field annotations that can only be used once per struct
+const SINGLE_FIELD_ATTRIBUTES: &[&str] = &["trailing", "unknown", "numeric_shorthand"];
+
+/// Field annotations that only apply along with another one
+const REQUIRED_ATTRIBUTES: &[(&str, &str)] = &[("default_expr", "skip")];
+
+/// Field annotations that cannot be used together
+const CONFLICTING_ATTRIBUTES: &[(&str, &str)] = &[
+    ("default_expr", "default"),
+    ("default_expr", "skip_with"),
+    ("from_str", "parse_with"),
+    ("from_str", "flatten"),
+    ("from_str", "skip"),
+];
+
+const INTEGER_TYPES: &[&str] = &["u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+
+/// Validates every `#[kurisu(...)]` annotation against the supported ones and the kind of value they accept
+pub fn attributes(attrs: &[Attribute], supported: &[(&str, &[Value])], errors: &mut Vec<syn::Error>) {
+    for attr in attrs.iter().filter(|a| a.path.is_ident("kurisu")) {
+        let nested = match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested,
+            Ok(Meta::Path(_)) => continue,
+            Ok(meta) => {
+                errors.push(syn::Error::new(meta.span(), "expected #[kurisu(...)]"));
+                continue;
+            }
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+
+        for item in nested.iter() {
+            let (path, lit) = match item {
+                NestedMeta::Meta(Meta::Path(path)) => (path, None),
+                NestedMeta::Meta(Meta::NameValue(name_value)) => (&name_value.path, Some(&name_value.lit)),
+                _ => {
+                    errors.push(syn::Error::new(item.span(), "expected `name` or `name = value`"));
+                    continue;
+                }
+            };
+
+            let ident = match path.get_ident() {
+                Some(ident) => ident.to_string(),
+                None => {
+                    errors.push(syn::Error::new(path.span(), "expected a single identifier"));
+                    continue;
+                }
+            };
+
+            match supported.iter().find(|(name, _)| *name == ident) {
+                Some((name, values)) => {
+                    if !values.iter().any(|v| value_matches(*v, lit)) {
+                        let span = lit.map_or(path.span(), |l| l.span());
+                        errors.push(syn::Error::new(span, value_expectation(name, values)));
+                    }
+                }
+                None => {
+                    let mut message = format!("unknown kurisu attribute `{}`", ident);
+                    if let Some(suggestion) = suggest(&ident, supported.iter().map(|(name, _)| *name)) {
+                        message = format!("{}, did you mean `{}`?", message, suggestion);
+                    }
+
+                    errors.push(syn::Error::new(path.span(), message));
+                }
+            }
+        }
+    }
+}
+
+fn value_matches(value: Value, lit: Option<&Lit>) -> bool {
+    match (value, lit) {
+        (Value::Flag, None) => true,
+        (Value::Str, Some(Lit::Str(_))) => true,
+        (Value::Int, Some(Lit::Int(_))) => true,
+        (Value::Char, Some(Lit::Str(s))) => s.value().chars().count() == 1,
+        (Value::Keyword(keyword), Some(Lit::Str(s))) => s.value() == keyword,
+        _ => false,
+    }
+}
+
+fn value_expectation(name: &str, values: &[Value]) -> String {
+    let expectations: Vec<String> = values
+        .iter()
+        .map(|v| match v {
+            Value::Flag => format!("`{}`", name),
+            Value::Str => format!("`{} = \"...\"`", name),
+            Value::Int => format!("an integer `{} = 1`", name),
+            Value::Char => format!("a single character `{} = \"b\"`", name),
+            Value::Keyword(keyword) => format!("`{} = \"{}\"`", name, keyword),
+        })
+        .collect();
+
+    format!("`{}` expects {}", name, expectations.join(" or "))
+}
+
+/// Closest candidate name, if any is close enough to be a likely typo
+fn suggest<'a>(ident: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    candidates
+        .map(|name| (name, distance(ident, name)))
+        .filter(|(name, distance)| *distance <= 2 || common_prefix(ident, name) >= 4)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(name, _)| name)
+}
+
+fn common_prefix(a: &str, b: &str) -> usize {
+    a.chars().zip(b.chars()).take_while(|(a, b)| a == b).count()
+}
+
+/// Levenshtein distance between two strings
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Validates the positional arguments layout: no duplicates and, unless an infinite positional argument
+/// absorbs them, no gaps between `pos = 1` and the highest position
+pub fn positions(fields: &syn::FieldsNamed, errors: &mut Vec<syn::Error>) {
+    let mut has_infinite = false;
+    let mut positions: Vec<(i64, &syn::Field, proc_macro2::Span)> = Vec::new();
+    for field in fields.named.iter() {
+        let attrs = crate::meta_attributes(&field.attrs);
+        if crate::is_field_skipped(field, &attrs) {
+            continue;
+        }
+
+        for (_, value) in attrs.iter().filter(|(ident, _)| ident == "pos") {
+            if value.is_none() {
+                has_infinite = true;
+            } else if let Some(Lit::Str(lit)) = value {
+                positions.push((-1, field, lit.span()));
+            } else if let Some(Lit::Int(lit)) = value {
+                match lit.base10_parse::<i64>() {
+                    Ok(pos) if pos > 0 || pos == -1 => positions.push((pos, field, lit.span())),
+                    Ok(_) => errors.push(syn::Error::new(lit.span(), "`pos` must be a positive integer")),
+                    Err(error) => errors.push(error),
+                }
+            }
+        }
+    }
+
+    for (i, (pos, field, span)) in positions.iter().enumerate() {
+        if let Some((_, other, _)) = positions.iter().take(i).find(|(p, _, _)| p == pos) {
+            let message = format!("position {} is already bound to field `{}`", pos, other.ident.as_ref().unwrap());
+            errors.push(syn::Error::new(*span, message));
+        } else if !has_infinite && *pos > 1 && !positions.iter().any(|(p, _, _)| *p == pos - 1) {
+            let name = field.ident.as_ref().unwrap();
+            errors.push(syn::Error::new(
+                *span,
+                format!("field `{}` has position {} but position {} is missing", name, pos, pos - 1),
+            ));
+        }
+    }
+}
+
+/// Validates annotations that can only be used on a single field, and on which type
+pub fn single_fields(fields: &syn::FieldsNamed, auto_shorts: bool, errors: &mut Vec<syn::Error>) {
+    for name in SINGLE_FIELD_ATTRIBUTES {
+        let mut annotated: Option<&syn::Field> = None;
+        for field in fields.named.iter() {
+            let attrs = crate::meta_attributes(&field.attrs);
+            if let Some((ident, _)) = attrs.iter().find(|(ident, _)| ident == name) {
+                if let Some(other) = annotated {
+                    let message = format!("`{}` is already used on field `{}`", name, other.ident.as_ref().unwrap());
+                    errors.push(syn::Error::new(ident.span(), message));
+                }
+
+                let ty = &field.ty;
+                let ty_string = quote::quote!(#ty).to_string();
+                if *name == "numeric_shorthand" && !INTEGER_TYPES.contains(&ty_string.as_str()) {
+                    errors.push(syn::Error::new(ty.span(), "`numeric_shorthand` requires an integer field"));
+                }
+
+                // `-NUM` is rewritten into the option's flag, a field without one could never receive it
+                let has = |annotation: &str| attrs.iter().any(|(ident, _)| ident == annotation);
+                let has_flag = !has("pos") && !has("trailing") && !has("unknown") && (!has("nolong") || has("short") || auto_shorts);
+                if *name == "numeric_shorthand" && !has_flag {
+                    errors.push(syn::Error::new(
+                        ident.span(),
+                        "`numeric_shorthand` requires a field with a short or long flag",
+                    ));
+                }
+
+                annotated = Some(field);
+            }
+        }
+    }
+}
+
+/// Validates that `num_values` is used on a tuple or an array of that many elements, or a collection such as `Vec`
+pub fn num_values(fields: &syn::FieldsNamed, errors: &mut Vec<syn::Error>) {
+    for field in fields.named.iter() {
+        for (ident, value) in crate::meta_attributes(&field.attrs) {
+            let lit = match value {
+                Some(Lit::Int(lit)) if ident == "num_values" => lit,
+                _ => continue,
+            };
+
+            let num_values = match lit.base10_parse::<usize>() {
+                Ok(num_values) if num_values > 0 => num_values,
+                Ok(_) => {
+                    errors.push(syn::Error::new(lit.span(), "`num_values` must be a positive integer"));
+                    continue;
+                }
+                Err(error) => {
+                    errors.push(error);
+                    continue;
+                }
+            };
+
+            match &field.ty {
+                syn::Type::Tuple(tuple) if tuple.elems.len() != num_values => {
+                    let message = format!("`num_values = {}` does not match a tuple of {} elements", num_values, tuple.elems.len());
+                    errors.push(syn::Error::new(lit.span(), message));
+                }
+                syn::Type::Tuple(_) => {}
+                syn::Type::Array(array) => {
+                    if let syn::Expr::Lit(syn::ExprLit { lit: Lit::Int(len), .. }) = &array.len {
+                        if len.base10_parse::<usize>().ok() != Some(num_values) {
+                            let message = format!("`num_values = {}` does not match an array of {} elements", num_values, len);
+                            errors.push(syn::Error::new(lit.span(), message));
+                        }
+                    }
+                }
+                // Whether other types take any number of values is checked against their `Parser` by the derive
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Validates the annotations used together on a field, fields prefixed by `_` count as annotated with `skip`,
+/// and so do fields with `skip_with` when conflicting with it
+pub fn combinations(fields: &syn::FieldsNamed, errors: &mut Vec<syn::Error>) {
+    for field in fields.named.iter() {
+        let attrs = crate::meta_attributes(&field.attrs);
+        let find = |name: &str| attrs.iter().find(|(ident, _)| ident == name).map(|(ident, _)| ident);
+        let prefixed = field.ident.as_ref().unwrap().to_string().starts_with('_');
+        for (name, required) in REQUIRED_ATTRIBUTES {
+            if let (Some(ident), None, false) = (find(name), find(required), *required == "skip" && prefixed) {
+                errors.push(syn::Error::new(ident.span(), format!("`{}` requires `{}`", name, required)));
+            }
+        }
+
+        let skipped = prefixed || find("skip_with").is_some();
+        for (name, other) in CONFLICTING_ATTRIBUTES {
+            let conflicts = find(other).is_some() || (*other == "skip" && skipped);
+            if let (Some(ident), true) = (find(name), conflicts) {
+                errors.push(syn::Error::new(ident.span(), format!("`{}` cannot be used with `{}`", name, other)));
+            }
+        }
+    }
+}
+
+/// Validates that fields referenced by other annotations exist.
+/// Structs with flattened fields can only be checked at runtime through `assert_schema()`.
+pub fn references(fields: &syn::FieldsNamed, errors: &mut Vec<syn::Error>) {
+    let mut names: Vec<String> = Vec::new();
+    for field in fields.named.iter() {
+        let attrs = crate::meta_attributes(&field.attrs);
+        if attrs.iter().any(|(ident, _)| ident == "flatten") {
+            return;
+        }
+
+        if !crate::is_field_skipped(field, &attrs) {
+            names.push(field.ident.as_ref().unwrap().to_string());
+        }
+    }
+
+    for field in fields.named.iter() {
+        for (ident, value) in crate::meta_attributes(&field.attrs) {
+            if let (true, Some(Lit::Str(lit))) = (ident == "required_if", value) {
+                if !names.contains(&lit.value()) {
+                    let mut message = format!("`required_if` references unknown field `{}`", lit.value());
+                    if let Some(suggestion) = suggest(&lit.value(), names.iter().map(|n| n.as_str())) {
+                        message = format!("{}, did you mean `{}`?", message, suggestion);
+                    }
+
+                    errors.push(syn::Error::new(lit.span(), message));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use bencher::{benchmark_group, benchmark_main, Bencher};
+use kurisu::arg::Precedence;
+use kurisu::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[allow(dead_code)]
+#[derive(Kurisu)]
+struct Yargs {
+    #[kurisu(short)]
+    verbose: bool,
+    #[kurisu(short)]
+    output: PathBuf,
+    #[kurisu(short)]
+    exclude: Vec<String>,
+    #[kurisu(pos)]
+    paths: Vec<PathBuf>,
+}
+
+fn env_args(paths: usize) -> Vec<String> {
+    let mut env_args = vec![String::from("-v"), String::from("--output"), String::from("out.txt")];
+    for i in 0..paths {
+        env_args.push(format!("src/module_{}/file_{}.rs", i / 100, i));
+        if i % 1000 == 0 {
+            env_args.push(format!("--exclude=target_{}", i));
+        }
+    }
+
+    env_args
+}
+
+/// Lexes, assigns and parses the command line values into the struct, as `from_args` does
+fn parse(env_args: &[String]) -> Yargs {
+    let mut info = Info {
+        name: None,
+        version: None,
+        desc: None,
+        doc: None,
+        allow_noargs: false,
+        allow_unknown: false,
+        allow_abbrev: false,
+        precedence: Precedence::default(),
+        implicit_env: false,
+        env: Env::default(),
+        env_args: env_args.to_vec(),
+        args: Yargs::get_args(),
+        tokens: Vec::new(),
+        names: HashMap::new(),
+    };
+    set_values(&mut info);
+
+    Yargs::from_info(&info)
+}
+
+fn paths_1k(bench: &mut Bencher) {
+    let env_args = env_args(1_000);
+    bench.iter(|| parse(&env_args));
+}
+
+fn paths_10k(bench: &mut Bencher) {
+    let env_args = env_args(10_000);
+    bench.iter(|| parse(&env_args));
+}
+
+fn paths_100k(bench: &mut Bencher) {
+    let env_args = env_args(100_000);
+    bench.iter(|| parse(&env_args));
+}
+
+benchmark_group!(benches, paths_1k, paths_10k, paths_100k);
+benchmark_main!(benches);
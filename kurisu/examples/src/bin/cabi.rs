@@ -1,12 +1,13 @@
 use kurisu::*;
 
+#[allow(dead_code)]
 #[derive(Debug, Kurisu)]
 #[kurisu(name = "cabi", version = "1.0.2", desc = "infinite arguments", auto_shorts)]
 struct Yargs {
     #[kurisu(pos = 1)]
     subcommand: String,
     #[kurisu(pos)]
-    args: String,
+    args: Vec<String>,
 }
 
 fn main() {
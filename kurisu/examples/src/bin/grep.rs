@@ -1,6 +1,7 @@
 use kurisu::*;
 use std::path::PathBuf;
 
+#[allow(dead_code)]
 #[derive(Debug, Kurisu)]
 #[kurisu(
     name = "grep",
@@ -108,10 +109,13 @@ struct Grep {
     before_context: usize,
     #[kurisu(short = "A", vname = "NUM", doc = "print NUM lines of trailing context")]
     after_context: usize,
-    #[kurisu(short = "C", vname = "NUM", doc = "print NUM lines of output context")]
+    #[kurisu(
+        short = "C",
+        vname = "NUM",
+        numeric_shorthand,
+        doc = "print NUM lines of output context, -NUM is the same as --context=NUM"
+    )]
     context: usize,
-    // #[kurisu(doc = "same as --context=NUM")] // Cant do -NUM  aka: -12
-    // num: usize,
     #[kurisu(
         vname = "WHEN",
         aliases = "colour",
@@ -1,6 +1,7 @@
 use kurisu::*;
 use std::path::PathBuf;
 
+#[allow(dead_code)]
 #[derive(Debug, Kurisu)]
 #[kurisu(name = "tldr", version = "1.0.2", desc = "Tool Long Didnt Read Example", auto_shorts)]
 /// some helpful text, tuturu ♫
@@ -27,7 +28,7 @@ struct Yargs {
     #[kurisu(short, nolong, default = "203")]
     /// Blah blah blog
     short: usize,
-    #[kurisu(short, parser = "parse_bobby")]
+    #[kurisu(short, parse_with = "parse_bobby")]
     bobby: String,
 }
 
@@ -37,13 +38,12 @@ pub fn my_func() -> i32 {
     ExitCode::OK.into()
 }
 
-pub fn parse_bobby(name: &str, info: &'_ Info) -> String {
-    let arg = info.args.iter().find(|a| name == a.name).expect("Infallible");
-    if arg.value.is_empty() {
-        return String::from("");
+pub fn parse_bobby(value: &str) -> Result<String, String> {
+    if value.is_empty() {
+        return Err(String::from("bobby cannot be empty"));
     }
 
-    arg.value[0].to_uppercase()
+    Ok(value.to_uppercase())
 }
 
 fn main() {
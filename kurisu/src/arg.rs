@@ -1,11 +1,14 @@
 mod error;
+mod index;
 mod parser;
 
+use crate::Env;
 use core::fmt;
 pub use error::Error;
-pub use parser::{Parser, VALUE_SEPARATOR};
+pub(crate) use error::Message;
+pub(crate) use index::ArgIndex;
+pub use parser::{is_multiple_with, FromValues, ParseWith, Parser, WithInfo, WithValue, WithValues};
 use std::cmp::Ordering;
-use std::collections::HashMap;
 use std::fmt::Display;
 
 const TYPES_NO_VALUE: &[&str] = &["bool"];
@@ -24,6 +27,8 @@ pub struct Arg<'a> {
     pub name: &'a str,
     pub vname: Option<&'a str>,
     pub value_type: &'a str,
+    /// Takes any number of values, set by the derive from `Parser::MULTIPLE` of the field's type
+    pub multiple: bool,
     pub position: Option<i8>,
     pub doc: Option<&'a str>,
     pub short: Option<&'a str>,
@@ -33,7 +38,17 @@ pub struct Arg<'a> {
     pub env: Option<&'a str>,
     pub env_prefix: Option<&'a str>,
     pub required_if: Option<&'a str>,
+    pub trailing: bool,
+    pub unknown: bool,
+    pub numeric_shorthand: bool,
     pub default: &'a str,
+    pub default_missing: Option<&'a str>,
+    pub num_values: Option<usize>,
+    pub allow_hyphen_values: bool,
+    pub delimiter: Option<&'a str>,
+    pub no_delimiter: bool,
+    pub env_delimiter: Option<&'a str>,
+    pub repeat: Repeat,
     pub value: Vec<String>,
     pub occurrences: usize,
 }
@@ -64,8 +79,17 @@ impl<'a> Display for Arg<'a> {
 
         let multiple = if self.is_value_multiple() { "..." } else { "" };
         let value = if !self.is_value_none() {
-            let value_name = if self.vname.is_some() { self.vname.unwrap() } else { self.name };
-            format!(" <{}>{}", value_name.to_uppercase(), multiple)
+            let value_name = self.vname.unwrap_or(self.name);
+            if self.default_missing.is_some() {
+                format!("[=<{}>]{}", value_name.to_uppercase(), multiple)
+            } else {
+                // Each value of an option taking several of them can be named, example: `vname = "old,new"`
+                let names: Vec<&str> = value_name.split(',').collect();
+                let values: Vec<String> = (0..self.num_values.unwrap_or(1))
+                    .map(|i| format!("<{}>", names.get(i).or_else(|| names.last()).unwrap_or(&"").to_uppercase()))
+                    .collect();
+                format!(" {}{}", values.join(" "), multiple)
+            }
         } else {
             String::from("")
         };
@@ -116,121 +140,247 @@ impl<'a> Arg<'a> {
     }
 
     fn partial_eq_string<S: AsRef<str>>(&self, value: S) -> bool {
+        // Only the flag name is compared, exactly, a value assigned with `=` is ignored
         let value = value.as_ref();
-        if !value.starts_with('-') {
-            return false;
+        let name = value.split('=').next().unwrap_or(value);
+        if let Some(long) = name.strip_prefix("--") {
+            return self.long.is_some() && self.get_longs().any(|l| l == long);
         }
 
-        if value.starts_with("--") && self.long.is_some() {
-            if !self.aliases.is_empty() {
-                for alias in self.aliases.iter().filter(|a| a.len() > 1) {
-                    if value.starts_with(&format!("--{}", alias)) {
-                        return true;
-                    }
-                }
-            }
-            return value.starts_with(&format!("--{}", self.long.expect("Infallible")));
+        if let Some(short) = name.strip_prefix('-') {
+            return self.short.is_some() && self.get_shorts().any(|s| s == short);
         }
 
-        if value.starts_with('-') && self.short.is_some() {
-            if !self.aliases.is_empty() {
-                for alias in self.aliases.iter().filter(|a| a.len() == 1) {
-                    if value.starts_with(&format!("-{}", alias)) {
-                        return true;
-                    }
-                }
-            }
-            return value.starts_with(&format!("-{}", self.short.expect("Infallible")));
+        false
+    }
+
+    /// Long flag with its dashes, or the short flag if it has no long, example: `--context`
+    pub fn get_flag_name(&self) -> Option<String> {
+        match (self.long, self.short) {
+            (Some(long), _) => Some(format!("--{}", long)),
+            (None, Some(short)) => Some(format!("-{}", short)),
+            (None, None) => None,
         }
+    }
 
-        false
+    /// Short flag followed by its single letter aliases
+    pub fn get_shorts(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.short.into_iter().chain(self.aliases.iter().copied().filter(|a| a.len() == 1))
+    }
+
+    /// Long flag followed by its aliases
+    pub fn get_longs(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.long.into_iter().chain(self.aliases.iter().copied().filter(|a| a.len() > 1))
     }
 
     pub fn is_value_required(&self) -> bool {
         self.default.is_empty() && !TYPES_NO_VALUE.contains(&self.value_type)
     }
 
+    /// Whether the argument takes any number of values, a collection such as `Vec<T>` or `HashSet<T>`, or an array `[T; N]`
     pub fn is_value_multiple(&self) -> bool {
-        self.value_type.starts_with("Vec")
+        self.multiple
+    }
+
+    /// Delimiter splitting a value into multiple ones, `,` by default for multiple values
+    pub fn get_delimiter(&self) -> Option<&'a str> {
+        if self.no_delimiter || !self.is_value_multiple() {
+            return None;
+        }
+
+        Some(self.delimiter.unwrap_or(",")).filter(|d| !d.is_empty())
+    }
+
+    /// Splits a value on the delimiter, which can be escaped by a backslash, example: `a\,b.txt,c.txt`
+    pub fn split_value(&self, value: &str) -> Vec<String> {
+        match self.get_delimiter() {
+            Some(delimiter) => split_escaped(value, delimiter),
+            None => vec![value.to_string()],
+        }
+    }
+
+    /// Delimiter splitting an environment variable into multiple values, the one the derive sets from the field's type,
+    /// example: `:` for paths like `PATH`, otherwise the command line delimiter
+    pub fn get_env_delimiter(&self) -> Option<&'a str> {
+        if !self.is_value_multiple() {
+            return None;
+        }
+
+        match self.env_delimiter {
+            Some(env_delimiter) => Some(env_delimiter).filter(|d| !d.is_empty()),
+            None => self.get_delimiter(),
+        }
     }
 
     pub fn is_value_none(&self) -> bool {
         TYPES_NO_VALUE.contains(&self.value_type)
     }
 
-    pub fn set_value(&'_ mut self, args: &[String], positions: &[i8]) {
-        let mut pos = 1;
-        let mut options_ended = false;
-        for (i, arg) in args.iter().enumerate() {
-            // We only drop the first --
-            if !options_ended && arg.len() == 2 && arg == "--" {
-                options_ended = true;
-                continue;
-            }
+    /// Whether an argument taking a single value was given more than once, example: `--color=auto --color=never`
+    /// or more than one value for an infinite positional argument
+    pub fn is_value_repeated(&self) -> bool {
+        self.value.len() > 1 && self.num_values.is_none() && !self.is_value_multiple()
+    }
 
-            if self.eq(arg) {
-                if arg.contains('=') {
-                    let value: Vec<&str> = arg.splitn(2, '=').collect();
-                    self.occurrences += 1;
-                    self.value.push(value[1].to_owned());
-                    continue;
-                }
+    /// Values given to the parser, only one of them is kept for a repeated single value argument according to `repeat`
+    pub fn get_values(&self) -> &[String] {
+        if !self.is_value_repeated() {
+            return &self.value;
+        }
 
-                self.occurrences += 1;
+        match self.repeat {
+            Repeat::First => &self.value[..1],
+            Repeat::Last | Repeat::Error => &self.value[self.value.len() - 1..],
+        }
+    }
 
-                // If we have a short, nolong u8 we put the number of occurrences in its value
-                if self.value_type == "u8" && self.long.is_none() && self.short.is_some() {
-                    self.value = vec![format!("{}", self.occurrences)];
-                } else if self.is_value_none() {
-                    self.value.push(String::from("true"));
-                }
-            } else if options_ended || !arg.starts_with('-') || (arg.starts_with('-') && arg.len() == 1) {
-                if let Some(position) = self.position {
-                    if position != pos && position != 0 && position != -1 {
-                        pos += 1;
-                        continue;
-                    }
-
-                    // If the arg is infinite but another arg has this position
-                    if position == 0 && (positions.contains(&pos) || (positions.contains(&-1) && (i + 1) == args.len())) {
-                        pos += 1;
-                        continue;
-                    }
-
-                    // If we seek the last argument position
-                    if position == -1 && (i + 1) != args.len() {
-                        pos += 1;
-                        continue;
-                    }
-
-                    self.occurrences += 1;
-                    self.value.push(arg.clone());
-                    pos += 1;
-                }
-            }
+    /// A short, nolong u8 flag takes no value, the number of its occurrences is its value, example: `-vvv`
+    pub fn is_occurrence_count(&self) -> bool {
+        self.value_type == "u8" && self.long.is_none() && self.short.is_some()
+    }
+
+    /// Records an occurrence of this flag or option with the values that follow it, example: `--color` or `--color=never`
+    pub fn add_occurrence(&mut self, values: &[String]) {
+        self.occurrences += 1;
+        if !values.is_empty() {
+            self.value.extend_from_slice(values);
+            return;
+        }
+
+        if self.is_occurrence_count() {
+            self.value = vec![format!("{}", self.occurrences)];
+        } else if self.is_value_none() {
+            self.value.push(String::from("true"));
+        } else if let Some(default_missing) = self.default_missing {
+            self.value.push(default_missing.to_string());
         }
+    }
+
+    /// Records a positional value of this argument
+    pub fn add_positional(&mut self, arg: &str) {
+        self.occurrences += 1;
+        self.value.push(arg.to_string());
+    }
 
+    /// Assigns the environment variable or the `default` value, in the given order, when missing from the command line
+    pub fn set_fallback_value(&mut self, precedence: Precedence, env: &Env) {
         if !self.value.is_empty() {
             return;
         }
 
-        if !self.default.is_empty() {
-            self.value.push(self.default.to_string());
-        } else {
-            let vars: HashMap<String, String> = std::env::vars().collect();
-            for (key, value) in vars {
-                let mut env_var = self.name.to_string();
-                if self.env.is_some() {
-                    env_var = self.env.expect("Infallible").to_string();
-                } else if self.env_prefix.is_some() {
-                    env_var = format!("{}{}", self.env_prefix.expect("Infallible"), self.name)
-                }
+        let value = match precedence {
+            Precedence::CliEnvDefault => self.get_env_value(env).or_else(|| self.get_default_value()),
+            Precedence::CliDefaultEnv => self.get_default_value().or_else(|| self.get_env_value(env)),
+        };
 
-                if key.to_lowercase() == env_var.to_lowercase() {
-                    self.value.push(value);
-                    break;
-                }
-            }
+        if let Some(value) = value {
+            self.value.extend(value);
+        }
+    }
+
+    /// Names of the environment variables given by `env`, tried in order, example: `env = "DATABASE_URL,DB_URL"`,
+    /// or by `env_prefix` followed by the field's name in SCREAMING_SNAKE_CASE, example: `MYAPP_MAX_COUNT`
+    pub fn get_env_names(&self) -> Vec<String> {
+        match (self.env, self.env_prefix) {
+            (Some(env), _) => env.split(',').map(|e| e.trim().to_string()).filter(|e| !e.is_empty()).collect(),
+            (None, Some(env_prefix)) => vec![format!("{}{}", env_prefix, self.name.to_uppercase())],
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Prior behavior of `implicit_env`: the `default` value, or else any environment variable
+    /// matching `env`, `env_prefix` or the field's name whatever its case, when missing from the command line
+    pub fn set_implicit_fallback_value(&mut self, env: &Env) {
+        if !self.value.is_empty() {
+            return;
+        }
+
+        let env_vars = self.get_env_names();
+        let env_vars = if env_vars.is_empty() { vec![self.name.to_string()] } else { env_vars };
+        let value = self.get_default_value().or_else(|| {
+            env_vars
+                .iter()
+                .find_map(|env_var| env.get_ignore_case(env_var))
+                .map(|v| self.split_env_value(v))
+        });
+
+        if let Some(value) = value {
+            self.value.extend(value);
+        }
+    }
+
+    fn get_env_value(&self, env: &Env) -> Option<Vec<String>> {
+        let value = self.get_env_names().iter().find_map(|env_var| env.get(env_var))?;
+        Some(self.split_env_value(value))
+    }
+
+    fn split_env_value(&self, value: &str) -> Vec<String> {
+        match self.get_env_delimiter() {
+            Some(delimiter) => split_escaped(value, delimiter).into_iter().filter(|v| !v.is_empty()).collect(),
+            None => vec![value.to_string()],
         }
     }
+
+    fn get_default_value(&self) -> Option<Vec<String>> {
+        Some(self.split_value(self.default)).filter(|_| !self.default.is_empty())
+    }
+}
+
+/// Splits a value on the delimiter, unless escaped by a backslash
+fn split_escaped(value: &str, delimiter: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut rest = value;
+    while let Some(c) = rest.chars().next() {
+        if c == '\\' && rest[1..].starts_with(delimiter) {
+            current.push_str(delimiter);
+            rest = &rest[1 + delimiter.len()..];
+        } else if rest.starts_with(delimiter) {
+            values.push(current);
+            current = String::new();
+            rest = &rest[delimiter.len()..];
+        } else {
+            current.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    values.push(current);
+    values
+}
+
+/// Order in which the value of an argument is looked up, the first source having one wins
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precedence {
+    /// Command line, then environment variable, then `default`
+    CliEnvDefault,
+    /// Command line, then `default`, then environment variable
+    CliDefaultEnv,
+}
+
+// `#[default]` on enum variants requires Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for Precedence {
+    fn default() -> Self {
+        Precedence::CliEnvDefault
+    }
+}
+
+/// Which value is kept when an argument taking a single value is given more than once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    /// The last one given wins
+    Last,
+    /// The first one given wins
+    First,
+    /// Given more than once is a usage error
+    Error,
+}
+
+// `#[default]` on enum variants requires Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for Repeat {
+    fn default() -> Self {
+        Repeat::Last
+    }
 }
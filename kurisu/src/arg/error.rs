@@ -1,4 +1,5 @@
-use crate::Arg;
+use crate::{Arg, ExitCode};
+use std::fmt;
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
@@ -6,7 +7,99 @@ pub enum Error {
     CustomArg(Arg<'static>, String),
     NoArgs,
     Invalid(String),
+    Ambiguous(String, Vec<String>),
     RequiresPositional(Arg<'static>),
     RequiresValue(Arg<'static>),
     RequiresValueIf(Arg<'static>, Box<Arg<'static>>),
+    Repeated(Arg<'static>),
+}
+
+impl Error {
+    /// Usage error naming the argument whose value its type or its `parse_with` callback rejects
+    pub(crate) fn invalid_value(arg: &Arg<'static>, value: &str, error: impl fmt::Display) -> Self {
+        Error::CustomArg(arg.clone(), format!("has an invalid value \"{}\": {}", value, error))
+    }
+
+    /// Exit code the application should terminate with for this error
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Error::Custom(_)
+            | Error::CustomArg(_, _)
+            | Error::NoArgs
+            | Error::Invalid(_)
+            | Error::Ambiguous(_, _)
+            | Error::RequiresPositional(_)
+            | Error::RequiresValue(_)
+            | Error::RequiresValueIf(_, _)
+            | Error::Repeated(_) => ExitCode::USAGE,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Error::Custom(text) => Message::Custom(text),
+            Error::CustomArg(arg, text) => Message::CustomArg(arg, text),
+            Error::NoArgs => Message::NoArgs,
+            Error::Invalid(arg) => Message::Invalid(arg),
+            Error::Ambiguous(arg, candidates) => Message::Ambiguous(arg, candidates),
+            Error::RequiresPositional(arg) => Message::RequiresPositional(arg),
+            Error::RequiresValue(arg) | Error::RequiresValueIf(arg, _) => Message::RequiresValue(arg),
+            Error::Repeated(arg) => Message::Repeated(arg),
+        };
+
+        message.fmt(f)
+    }
+}
+
+/// Error borrowing its argument, which does not need to be `'static`, the message text of both
+/// `Error` and the `mayuri` print functions
+pub(crate) enum Message<'e, 'a> {
+    Custom(&'e str),
+    CustomArg(&'e Arg<'a>, &'e str),
+    NoArgs,
+    Invalid(&'e str),
+    Ambiguous(&'e str, &'e [String]),
+    RequiresPositional(&'e Arg<'a>),
+    RequiresValue(&'e Arg<'a>),
+    Repeated(&'e Arg<'a>),
+}
+
+impl fmt::Display for Message<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Message::Custom(text) => write!(f, "{}", text),
+            Message::CustomArg(arg, text) => write!(f, "{} {}", arg.to_string().trim(), text),
+            Message::NoArgs => write!(f, "missing arguments"),
+            Message::Invalid(arg) => write!(f, "unrecognized option {}", arg),
+            Message::Ambiguous(arg, candidates) => write!(f, "ambiguous option {} could match {}", arg, candidates.join(", ")),
+            Message::RequiresPositional(arg) => write!(f, "missing argument {}", arg.to_string().trim()),
+            Message::RequiresValue(arg) => write!(f, "missing value for option {}", arg.to_string().trim()),
+            Message::Repeated(arg) if arg.position.is_some() || arg.trailing || arg.unknown => {
+                write!(f, "argument {} cannot be given multiple values", arg.to_string().trim())
+            }
+            Message::Repeated(arg) => write!(f, "option {} cannot be used multiple times", arg.to_string().trim()),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<Error> for ExitCode {
+    fn from(error: Error) -> ExitCode {
+        error.exit_code()
+    }
+}
+
+impl From<&Error> for ExitCode {
+    fn from(error: &Error) -> ExitCode {
+        error.exit_code()
+    }
+}
+
+impl From<Error> for i32 {
+    fn from(error: Error) -> i32 {
+        error.exit_code().into()
+    }
 }
@@ -0,0 +1,34 @@
+use crate::Arg;
+use std::collections::HashMap;
+
+/// Arguments looked up by their flags, `--long`, `-s` and their aliases, built once per parse
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ArgIndex {
+    flags: HashMap<String, usize>,
+}
+
+impl ArgIndex {
+    pub fn new(args: &[Arg<'_>]) -> Self {
+        let mut flags = HashMap::new();
+        for (i, arg) in args.iter().enumerate() {
+            if arg.long.is_some() {
+                for long in arg.get_longs() {
+                    flags.entry(format!("--{}", long)).or_insert(i);
+                }
+            }
+
+            if arg.short.is_some() {
+                for short in arg.get_shorts() {
+                    flags.entry(format!("-{}", short)).or_insert(i);
+                }
+            }
+        }
+
+        ArgIndex { flags }
+    }
+
+    /// Position of the argument matching the flag exactly, example: `--color` or `-c`
+    pub fn get(&self, flag: &str) -> Option<usize> {
+        self.flags.get(flag).copied()
+    }
+}
@@ -1,116 +1,330 @@
-pub const VALUE_SEPARATOR: &str = "(_(__)===D";
-
 #[cfg(feature = "parser_extras")]
 mod extras;
+use crate::arg::Error;
+use crate::Info;
 #[cfg(feature = "parser_extras")]
 pub use extras::*;
+use std::collections::{BTreeSet, BinaryHeap, HashSet, LinkedList, VecDeque};
+use std::fmt::Display;
+use std::hash::Hash;
+use std::marker::PhantomData;
 use std::path::PathBuf;
 
+/// Parses the raw values of an argument, in the order they were given. A collection of your own takes
+/// any number of values by setting `MULTIPLE`, example: `impl<T: Parser> Parser for MyList<T>`
 pub trait Parser {
-    fn parse(value: &str) -> Self;
+    /// Whether the type takes any number of values, true for arrays and the collections of `std::collections`.
+    /// A single value type only keeps one of them, according to `#[kurisu(repeat)]`
+    const MULTIPLE: bool = false;
+
+    /// Delimiter splitting an environment variable into values when the field has no `env_delimiter`,
+    /// the command line one if `None`
+    const ENV_DELIMITER: Option<&'static str> = None;
+
+    fn parse(values: &[String]) -> Self;
+
+    /// Checks that the values can be parsed, `parse` itself falls back to the type's default value
+    fn validate(values: &[String]) -> Result<(), String>;
 }
 
-impl Parser for String {
-    fn parse(value: &str) -> Self {
-        value.replace(VALUE_SEPARATOR, " ")
-    }
+/// Value of an argument taking a single one, the last given if there are more, empty if there are none
+pub(crate) fn single_value(values: &[String]) -> &str {
+    values.last().map(|v| v.as_str()).unwrap_or_default()
 }
 
-impl Parser for Vec<String> {
-    fn parse(value: &str) -> Self {
-        if value.is_empty() {
-            return Vec::new();
-        }
+impl Parser for String {
+    fn parse(values: &[String]) -> Self {
+        single_value(values).to_string()
+    }
 
-        value.split(VALUE_SEPARATOR).map(|v| v.to_string()).collect()
+    /// Any text is a valid `String`
+    fn validate(_values: &[String]) -> Result<(), String> {
+        Ok(())
     }
 }
 
 impl Parser for bool {
-    fn parse(value: &str) -> Self {
-        value.parse::<bool>().unwrap_or_default()
+    fn parse(values: &[String]) -> Self {
+        single_value(values).parse::<bool>().unwrap_or_default()
+    }
+
+    fn validate(values: &[String]) -> Result<(), String> {
+        single_value(values).parse::<bool>().map(|_| ()).map_err(|e| e.to_string())
     }
 }
 
 impl Parser for u8 {
-    fn parse(value: &str) -> Self {
-        value.parse::<u8>().unwrap_or_default()
+    fn parse(values: &[String]) -> Self {
+        single_value(values).parse::<u8>().unwrap_or_default()
+    }
+
+    fn validate(values: &[String]) -> Result<(), String> {
+        single_value(values).parse::<u8>().map(|_| ()).map_err(|e| e.to_string())
     }
 }
 
 impl Parser for usize {
-    fn parse(value: &str) -> Self {
-        value.parse::<usize>().unwrap_or_default()
+    fn parse(values: &[String]) -> Self {
+        single_value(values).parse::<usize>().unwrap_or_default()
+    }
+
+    fn validate(values: &[String]) -> Result<(), String> {
+        single_value(values).parse::<usize>().map(|_| ()).map_err(|e| e.to_string())
     }
 }
 
-impl Parser for Vec<usize> {
-    fn parse(value: &str) -> Self {
-        if value.is_empty() {
-            return Vec::new();
-        }
+impl Parser for isize {
+    fn parse(values: &[String]) -> Self {
+        single_value(values).parse::<isize>().unwrap_or_default()
+    }
 
-        value.split(VALUE_SEPARATOR).map(|v| v.parse::<usize>().unwrap_or_default()).collect()
+    fn validate(values: &[String]) -> Result<(), String> {
+        single_value(values).parse::<isize>().map(|_| ()).map_err(|e| e.to_string())
     }
 }
 
-impl Parser for isize {
-    fn parse(value: &str) -> Self {
-        value.parse::<isize>().unwrap_or_default()
+impl Parser for f64 {
+    fn parse(values: &[String]) -> Self {
+        single_value(values).parse::<f64>().unwrap_or_default()
+    }
+
+    fn validate(values: &[String]) -> Result<(), String> {
+        single_value(values).parse::<f64>().map(|_| ()).map_err(|e| e.to_string())
     }
 }
 
-impl Parser for Vec<isize> {
-    fn parse(value: &str) -> Self {
+impl Parser for PathBuf {
+    /// Paths are listed like in `PATH`, example: `MYAPP_INCLUDE=a:b:c`
+    const ENV_DELIMITER: Option<&'static str> = Some(":");
+
+    fn parse(values: &[String]) -> Self {
+        let value = single_value(values);
         if value.is_empty() {
-            return Vec::new();
+            return PathBuf::default();
         }
 
-        value.split(VALUE_SEPARATOR).map(|v| v.parse::<isize>().unwrap_or_default()).collect()
+        PathBuf::from(value)
     }
-}
 
-impl Parser for f64 {
-    fn parse(value: &str) -> Self {
-        value.parse::<f64>().unwrap_or_default()
+    /// Any text is a valid path, whether it exists is up to the application
+    fn validate(_values: &[String]) -> Result<(), String> {
+        Ok(())
     }
 }
 
-impl Parser for Vec<f64> {
-    fn parse(value: &str) -> Self {
-        if value.is_empty() {
-            return Vec::new();
+/// Collections are parsed from one value per element, sets keep a single one of duplicated values
+macro_rules! impl_collection_parser {
+    ($collection:ident $(, $bound:path)*) => {
+        impl<T: Parser $(+ $bound)*> Parser for $collection<T> {
+            const MULTIPLE: bool = true;
+            const ENV_DELIMITER: Option<&'static str> = T::ENV_DELIMITER;
+
+            fn parse(values: &[String]) -> Self {
+                values.iter().map(|v| T::parse(std::slice::from_ref(v))).collect()
+            }
+
+            fn validate(values: &[String]) -> Result<(), String> {
+                values.iter().try_for_each(|v| T::validate(std::slice::from_ref(v)))
+            }
         }
 
-        value.split(VALUE_SEPARATOR).map(|v| v.parse::<f64>().unwrap_or_default()).collect()
+        impl<T> FromValues<T> for $collection<T>
+        where
+            Self: std::iter::FromIterator<T>,
+        {
+            fn from_values<I: Iterator<Item = T>>(values: I) -> Self {
+                values.collect()
+            }
+        }
+    };
+}
+
+impl_collection_parser!(Vec);
+impl_collection_parser!(VecDeque);
+impl_collection_parser!(LinkedList);
+impl_collection_parser!(HashSet, Eq, Hash);
+impl_collection_parser!(BTreeSet, Ord);
+impl_collection_parser!(BinaryHeap, Ord);
+
+/// Fields a `fn(&str) -> Result<T, E>` callback of `#[kurisu(parse_with = "my_func")]` is called for each value of,
+/// only the collections taking any number of values and arrays
+pub trait FromValues<T>: Sized {
+    fn from_values<I: Iterator<Item = T>>(values: I) -> Self;
+
+    /// Checks the number of values, any number by default
+    fn validate_count(_count: usize) -> Result<(), String> {
+        Ok(())
     }
 }
 
-impl Parser for PathBuf {
-    fn parse(value: &str) -> Self {
-        if value.is_empty() {
-            return PathBuf::default();
+/// Arrays are filled in order, missing elements fall back to their type's default value
+impl<T: Default, const N: usize> FromValues<T> for [T; N] {
+    fn from_values<I: Iterator<Item = T>>(mut values: I) -> Self {
+        [(); N].map(|_| values.next().unwrap_or_default())
+    }
+
+    fn validate_count(count: usize) -> Result<(), String> {
+        if count != N {
+            return Err(format!("expected {} values, got {}", N, count));
         }
 
-        PathBuf::from(value)
+        Ok(())
     }
 }
 
-impl Parser for Vec<PathBuf> {
-    fn parse(value: &str) -> Self {
-        if value.is_empty() {
-            return Vec::new();
+/// Arrays are parsed from one value per element, missing elements fall back to their type's default value
+impl<T: Parser, const N: usize> Parser for [T; N] {
+    const MULTIPLE: bool = true;
+    const ENV_DELIMITER: Option<&'static str> = T::ENV_DELIMITER;
+
+    fn parse(values: &[String]) -> Self {
+        let mut values = values.iter();
+        [(); N].map(|_| T::parse(values.next().map(std::slice::from_ref).unwrap_or_default()))
+    }
+
+    fn validate(values: &[String]) -> Result<(), String> {
+        if values.len() != N {
+            return Err(format!("expected {} values, got {}", N, values.len()));
         }
 
-        value
-            .split(VALUE_SEPARATOR)
-            .map(|v| {
-                if v.is_empty() {
-                    return PathBuf::default();
+        values.iter().try_for_each(|v| T::validate(std::slice::from_ref(v)))
+    }
+}
+
+/// Tuples are parsed from one value per element, example: `--rename OLD NEW` with `num_values = 2`,
+/// or from a single value split on `,` into as many elements, or on `x` into as many numbers, example: `--size 800x600`
+macro_rules! impl_tuple_parser {
+    ($($element:ident),+) => {
+        impl<$($element: Parser),+> Parser for ($($element,)+) {
+            fn parse(values: &[String]) -> Self {
+                let values = split_tuple::<Self>(values, [$(stringify!($element)),+].len());
+                let mut values = values.iter();
+                ($($element::parse(values.next().map(std::slice::from_ref).unwrap_or_default()),)+)
+            }
+
+            fn validate(values: &[String]) -> Result<(), String> {
+                let elements = [$(stringify!($element)),+].len();
+                let values = split_tuple::<Self>(values, elements);
+                if values.len() != elements {
+                    return Err(format!("expected {} values, got {}", elements, values.len()));
                 }
 
-                PathBuf::from(v)
-            })
-            .collect()
+                let mut values = values.iter();
+                $($element::validate(values.next().map(std::slice::from_ref).unwrap_or_default())?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_tuple_parser!(A, B);
+impl_tuple_parser!(A, B, C);
+impl_tuple_parser!(A, B, C, D);
+
+/// Elements of a tuple given as a single value, when splitting it on `,` gives exactly as many, or else on `x`
+/// only when it gives as many numbers the tuple accepts, example: `800x600` but not `box`
+fn split_tuple<P: Parser>(values: &[String], elements: usize) -> Vec<String> {
+    if let [value] = values {
+        let split: Vec<String> = value.split(',').map(String::from).collect();
+        if split.len() == elements {
+            return split;
+        }
+
+        let split: Vec<String> = value.split('x').map(String::from).collect();
+        let is_numeric = split.iter().all(|v| v.parse::<f64>().is_ok());
+        if split.len() == elements && is_numeric && P::validate(&split).is_ok() {
+            return split;
+        }
+    }
+
+    values.to_vec()
+}
+
+/// Callback of `#[kurisu(parse_with = "my_func")]`, either `fn(&str) -> Result<T, E>` called per raw value,
+/// for a single value or each one of a collection or an array, or `fn(name: &str, info: &Info) -> T` for advanced use.
+/// A `fn(&str) -> Result<T, E>` callback is called once when parsing and once again by `validate_usage`,
+/// it should have no side effects
+pub trait ParseWith<Marker, T> {
+    /// Whether the callback is called for each one of any number of values
+    const MULTIPLE: bool = false;
+
+    fn parse_with(&self, name: &str, info: &Info<'_>) -> T;
+
+    /// Usage error of the first value the callback rejects
+    fn validate_with(&self, _name: &str, _info: &Info<'static>) -> Option<Error> {
+        None
+    }
+}
+
+/// Whether a `parse_with` callback takes any number of values for a field of type `T`
+#[doc(hidden)]
+pub fn is_multiple_with<Marker, T, F: ParseWith<Marker, T>>(_callback: &F) -> bool {
+    F::MULTIPLE
+}
+
+/// `fn(name: &str, info: &Info) -> T` callbacks
+#[doc(hidden)]
+pub struct WithInfo;
+
+/// `fn(&str) -> Result<T, E>` callbacks of a single value
+#[doc(hidden)]
+pub struct WithValue<T, E>(PhantomData<fn() -> (T, E)>);
+
+/// `fn(&str) -> Result<T, E>` callbacks of each value of a collection or an array
+#[doc(hidden)]
+pub struct WithValues<T, E>(PhantomData<fn() -> (T, E)>);
+
+impl<F, T> ParseWith<WithInfo, T> for F
+where
+    F: Fn(&str, &Info<'_>) -> T,
+{
+    fn parse_with(&self, name: &str, info: &Info<'_>) -> T {
+        self(name, info)
+    }
+}
+
+impl<F, T, E> ParseWith<WithValue<T, E>, T> for F
+where
+    F: Fn(&str) -> Result<T, E>,
+    T: Default,
+    E: Display,
+{
+    /// The last value given, or the type's default value if missing or rejected
+    fn parse_with(&self, name: &str, info: &Info<'_>) -> T {
+        let arg = info.get_arg(name).expect("Infallible");
+        arg.get_values().last().and_then(|v| self(v).ok()).unwrap_or_default()
+    }
+
+    fn validate_with(&self, name: &str, info: &Info<'static>) -> Option<Error> {
+        let arg = info.get_arg(name).expect("Infallible");
+        let value = arg.get_values().last()?;
+        let error = self(value).err()?;
+        Some(Error::invalid_value(arg, value, error))
+    }
+}
+
+impl<F, T, E, C> ParseWith<WithValues<T, E>, C> for F
+where
+    F: Fn(&str) -> Result<T, E>,
+    E: Display,
+    C: FromValues<T>,
+{
+    const MULTIPLE: bool = true;
+
+    /// Every value given, without the rejected ones
+    fn parse_with(&self, name: &str, info: &Info<'_>) -> C {
+        let arg = info.get_arg(name).expect("Infallible");
+        C::from_values(arg.get_values().iter().filter_map(|v| self(v).ok()))
+    }
+
+    fn validate_with(&self, name: &str, info: &Info<'static>) -> Option<Error> {
+        let arg = info.get_arg(name).expect("Infallible");
+        let values = arg.get_values();
+        if let Some(error) = values.iter().find_map(|v| self(v).err().map(|error| Error::invalid_value(arg, v, error))) {
+            return Some(error);
+        }
+
+        let error = C::validate_count(values.len()).err().filter(|_| !values.is_empty())?;
+        Some(Error::invalid_value(arg, &values.join(" "), error))
     }
 }
@@ -1,8 +1,10 @@
+use super::single_value;
 use crate::arg::Parser;
 
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 impl Parser for IpAddr {
-    fn parse(value: &'_ str) -> Self {
+    fn parse(values: &[String]) -> Self {
+        let value = single_value(values);
         if let Ok(ip) = value.parse::<Ipv6Addr>() {
             IpAddr::V6(ip)
         } else if let Ok(ip) = value.parse::<Ipv4Addr>() {
@@ -11,13 +13,18 @@ impl Parser for IpAddr {
             IpAddr::V4(Ipv4Addr::UNSPECIFIED)
         }
     }
+
+    fn validate(values: &[String]) -> Result<(), String> {
+        single_value(values).parse::<IpAddr>().map(|_| ()).map_err(|e| e.to_string())
+    }
 }
 
 use std::str::FromStr;
 use url::Url;
 
 impl Parser for Url {
-    fn parse(value: &str) -> Self {
+    fn parse(values: &[String]) -> Self {
+        let value = single_value(values);
         if !value.is_empty() {
             if let Ok(url) = Url::from_str(value) {
                 return url;
@@ -28,4 +35,8 @@ impl Parser for Url {
 
         Url::from_str("data:text/plain,").unwrap()
     }
+
+    fn validate(values: &[String]) -> Result<(), String> {
+        Url::from_str(single_value(values)).map(|_| ()).map_err(|e| e.to_string())
+    }
 }
@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/// Snapshot of the environment variables, taken once per parse and looked up by name
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Env {
+    vars: HashMap<String, String>,
+    /// Name of the variables keyed by their lowercase name, for `implicit_env`
+    names_lowercase: HashMap<String, String>,
+}
+
+impl Env {
+    /// Environment variables of the current process, those that are not valid unicode are ignored
+    pub fn capture() -> Self {
+        std::env::vars_os()
+            .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+            .collect::<HashMap<String, String>>()
+            .into()
+    }
+
+    /// Value of the environment variable with the exact same name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(|v| v.as_str())
+    }
+
+    /// Value of the environment variable matching the name whatever its case, the one with the exact
+    /// same name first, or else the first name in sorted order, example: `FOO` before `Foo` for `foo`
+    pub fn get_ignore_case(&self, name: &str) -> Option<&str> {
+        self.get(name)
+            .or_else(|| self.names_lowercase.get(&name.to_lowercase()).and_then(|key| self.get(key)))
+    }
+}
+
+/// Only the names are shown, values can be secrets
+impl fmt::Debug for Env {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<&String> = self.vars.keys().collect();
+        names.sort();
+        f.debug_struct("Env").field("names", &names).finish_non_exhaustive()
+    }
+}
+
+impl From<HashMap<String, String>> for Env {
+    fn from(vars: HashMap<String, String>) -> Self {
+        let mut names: Vec<&String> = vars.keys().collect();
+        names.sort();
+
+        let mut names_lowercase: HashMap<String, String> = HashMap::new();
+        for name in names {
+            names_lowercase.entry(name.to_lowercase()).or_insert_with(|| name.clone());
+        }
+
+        Env { vars, names_lowercase }
+    }
+}
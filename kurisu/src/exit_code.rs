@@ -1,4 +1,8 @@
-// TODO: Implement Termination trait once stable
+use std::io::{self, ErrorKind};
+use std::process::Termination;
+
+/// Exit codes following the BSD `sysexits.h` semantics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ExitCode {
     OK,
     /// General Failure
@@ -67,3 +71,42 @@ impl From<ExitCode> for i32 {
         }
     }
 }
+
+impl From<ExitCode> for std::process::ExitCode {
+    fn from(code: ExitCode) -> std::process::ExitCode {
+        let code: i32 = code.into();
+        std::process::ExitCode::from(code as u8)
+    }
+}
+
+impl Termination for ExitCode {
+    fn report(self) -> std::process::ExitCode {
+        self.into()
+    }
+}
+
+impl From<&io::Error> for ExitCode {
+    fn from(error: &io::Error) -> ExitCode {
+        match error.kind() {
+            ErrorKind::NotFound => ExitCode::NOINPUT,
+            ErrorKind::PermissionDenied => ExitCode::NOPERM,
+            ErrorKind::AlreadyExists => ExitCode::CANTCREAT,
+            ErrorKind::InvalidInput | ErrorKind::InvalidData | ErrorKind::UnexpectedEof => ExitCode::DATAERR,
+            ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::AddrNotAvailable
+            | ErrorKind::Unsupported => ExitCode::UNAVAILABLE,
+            ErrorKind::AddrInUse | ErrorKind::OutOfMemory => ExitCode::OSERR,
+            ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted => ExitCode::TEMPFAIL,
+            _ => ExitCode::IOERR,
+        }
+    }
+}
+
+impl From<io::Error> for ExitCode {
+    fn from(error: io::Error) -> ExitCode {
+        ExitCode::from(&error)
+    }
+}
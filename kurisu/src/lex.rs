@@ -0,0 +1,194 @@
+use crate::arg::ArgIndex;
+use crate::{get_abbreviation_candidates, is_number, Arg, Info};
+use std::ops::Range;
+
+/// Kind of a command line token
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Short flag or option, example: `-v`, each letter of stacked flags like `-vvi` is its own token
+    Short,
+    /// Long flag or option, example: `--color`
+    Long,
+    /// Value of an option, example: `never` within `--color=never` or `--color never`
+    Value,
+    /// Positional argument, as well as every argument after `--`
+    Positional,
+    /// The first `--`, after which nothing is a flag or an option anymore
+    Separator,
+}
+
+/// Command line token pointing at where it comes from within the original arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    /// Index of the original argument
+    pub index: usize,
+    /// Byte range within the original argument
+    pub range: Range<usize>,
+    /// Name of the argument a flag, an option or a value belongs to, `None` if unknown and for positional arguments
+    pub name: Option<&'a str>,
+}
+
+impl<'a> Token<'a> {
+    /// Text of the token within the original arguments it was lexed from
+    pub fn text<'s>(&self, args: &'s [String]) -> &'s str {
+        &args[self.index][self.range.clone()]
+    }
+
+    /// Unknown flag or option as typed, with its value if assigned with `=`, example: `--colour=auto` or `-xyz`
+    pub(crate) fn unknown_text(&self, args: &[String]) -> String {
+        match self.range.start {
+            0 => args[self.index].clone(),
+            _ => format!("-{}", self.text(args)),
+        }
+    }
+}
+
+/// Splits the original command line arguments into typed tokens, without normalizing them. This is the
+/// tokenizer `from_args` parses with, its tokens are kept in `Info::tokens`, example:
+/// ```
+/// use kurisu::*;
+///
+/// #[derive(Kurisu)]
+/// struct Yargs {
+///     #[kurisu(short)]
+///     color: String,
+/// }
+///
+/// let args = vec!["-c".to_string(), "never".to_string()];
+/// let info = Yargs::get_info_instance(args.clone()).lock().unwrap();
+/// let tokens = kurisu::lex(&args, &info);
+/// assert_eq!(tokens[1].kind, TokenKind::Value);
+/// assert_eq!(tokens[1].name, Some("color"));
+/// assert_eq!(tokens[1].text(&args), "never");
+/// assert_eq!(tokens, info.tokens);
+/// ```
+pub fn lex<'a>(args: &[String], info: &Info<'a>) -> Vec<Token<'a>> {
+    tokenize(args, &info.args, info.allow_abbrev)
+}
+
+pub(crate) fn tokenize<'a>(args: &[String], kurisu_args: &[Arg<'a>], allow_abbrev: bool) -> Vec<Token<'a>> {
+    let index = ArgIndex::new(kurisu_args);
+    let find = |flag: &str| -> Option<&Arg<'a>> {
+        if let Some(i) = index.get(flag) {
+            return kurisu_args.get(i);
+        }
+
+        // Unambiguous abbreviation of a long option
+        if !allow_abbrev || !flag.starts_with("--") {
+            return None;
+        }
+
+        match get_abbreviation_candidates(flag, kurisu_args).as_slice() {
+            [long] => kurisu_args.iter().find(|a| a.long == Some(long)),
+            _ => None,
+        }
+    };
+
+    let numeric_shorthand = kurisu_args.iter().find(|a| a.numeric_shorthand);
+    let mut tokens = Vec::new();
+    let mut options_ended = false;
+    // Option waiting for values and how many
+    let mut pending: Option<(&Arg<'a>, usize)> = None;
+    for (i, arg) in args.iter().enumerate() {
+        let token = |kind: TokenKind, range: Range<usize>, name: Option<&'a str>| Token { kind, index: i, range, name };
+        if options_ended {
+            tokens.push(token(TokenKind::Positional, 0..arg.len(), None));
+            continue;
+        }
+
+        if let Some((karg, remaining)) = pending.take() {
+            let is_option = arg.starts_with('-') && arg.len() > 1 && !is_number(arg, Some(karg));
+            if arg != "--" && (karg.allow_hyphen_values || !is_option) {
+                tokens.push(token(TokenKind::Value, 0..arg.len(), Some(karg.name)));
+                if remaining > 1 {
+                    pending = Some((karg, remaining - 1));
+                }
+                continue;
+            }
+        }
+
+        if arg == "--" {
+            tokens.push(token(TokenKind::Separator, 0..arg.len(), None));
+            options_ended = true;
+            continue;
+        }
+
+        if arg.starts_with("--") {
+            let name_end = arg.find('=').unwrap_or(arg.len());
+            let karg = find(&arg[..name_end]);
+            tokens.push(token(TokenKind::Long, 0..name_end, karg.map(|a| a.name)));
+
+            let mut remaining = karg.map_or(0, get_value_count);
+            if name_end < arg.len() {
+                tokens.push(token(TokenKind::Value, name_end + 1..arg.len(), karg.map(|a| a.name)));
+                remaining = remaining.saturating_sub(1);
+            }
+
+            pending = karg.filter(|_| remaining > 0).map(|a| (a, remaining));
+            continue;
+        }
+
+        if arg.starts_with('-') && arg.len() > 1 && !is_number(arg, None) {
+            for (position, short) in arg.char_indices().skip(1) {
+                let start = if position == 1 { 0 } else { position };
+                let end = position + short.len_utf8();
+                let karg = match find(&format!("-{}", short)) {
+                    Some(karg) => karg,
+                    None => {
+                        // Unknown short flag, the rest of the argument is kept as typed
+                        tokens.push(token(TokenKind::Short, start..arg.len(), None));
+                        break;
+                    }
+                };
+
+                tokens.push(token(TokenKind::Short, start..end, Some(karg.name)));
+                // An occurrence counter only takes its value as the next argument, example: `-t 42`
+                let remaining = if karg.is_occurrence_count() { 1 } else { get_value_count(karg) };
+                let takes_value = !karg.is_value_none() && !karg.is_occurrence_count() || arg[end..].starts_with('=');
+                if takes_value && end < arg.len() {
+                    // Value attached to its short flag, example: `-iVALUE` or `-i=VALUE`
+                    let value_start = if arg[end..].starts_with('=') { end + 1 } else { end };
+                    tokens.push(token(TokenKind::Value, value_start..arg.len(), Some(karg.name)));
+                    pending = Some((karg, remaining.saturating_sub(1))).filter(|(_, r)| *r > 0);
+                    break;
+                }
+
+                pending = Some((karg, remaining)).filter(|(_, r)| *r > 0);
+            }
+            continue;
+        }
+
+        // Numeric shorthand `-NUM`, example: `-5` for `--context=5`
+        let is_shorthand = arg.len() > 1 && arg.starts_with('-') && arg.chars().skip(1).all(|c| c.is_ascii_digit());
+        if let (true, Some(karg)) = (is_shorthand, numeric_shorthand) {
+            tokens.push(token(TokenKind::Short, 0..1, Some(karg.name)));
+            tokens.push(token(TokenKind::Value, 1..arg.len(), Some(karg.name)));
+            continue;
+        }
+
+        tokens.push(token(TokenKind::Positional, 0..arg.len(), None));
+    }
+
+    tokens
+}
+
+/// Every token but values, each one followed by the values of its flag or option
+pub(crate) fn group<'t, 'a>(tokens: &'t [Token<'a>]) -> Vec<(&'t Token<'a>, &'t [Token<'a>])> {
+    let mut groups = Vec::new();
+    for (i, token) in tokens.iter().enumerate().filter(|(_, t)| t.kind != TokenKind::Value) {
+        let values = tokens[i + 1..].iter().take_while(|t| t.kind == TokenKind::Value).count();
+        groups.push((token, &tokens[i + 1..i + 1 + values]));
+    }
+
+    groups
+}
+
+/// Number of values following a flag or an option that are not attached to it
+pub(crate) fn get_value_count(arg: &Arg<'_>) -> usize {
+    if arg.is_value_none() || arg.is_occurrence_count() || arg.default_missing.is_some() {
+        return 0;
+    }
+
+    arg.num_values.unwrap_or(1)
+}
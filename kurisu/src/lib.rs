@@ -23,7 +23,8 @@
 //!     // In this case: usize::default()
 //!     let args = Yargs::from_args(env_vars);
 //!
-//!     // Returns an Option<kurisu::arg::Error> or None
+//!     // Returns an Option<kurisu::arg::Error> or None, the error implements `Display`
+//!     // and `std::error::Error` and knows its own `exit_code()`
 //!     let arg_error = kurisu::validate_usage(&args);
 //!
 //!     // If an error is present `print_usage_error` will std::process::exit()
@@ -72,8 +73,13 @@
 //! The infinite positional argument struct field type is defined by `Vec<T>` and one of the
 //! supported types.
 //!
+//! A positional argument can also be bound to the last position, `cp`/`mv` style: `SOURCES... DEST`.
+//!
 //! Arguments are always required. There is no way to make them optional.
 //!
+//! Everything following `--` is considered a positional argument, unless a trailing struct field
+//! is defined in which case it collects every raw value after `--`, example: `mycli run -- --not-mine`.
+//!
 //! ## Flags
 //! Prefixed by either `-` or `--`, examples: `mycli --my-flag`, `mycli -f`. Their struct field type
 //! is always a `bool`. They never have a value associated to them, example: `mycli -f value`,
@@ -89,7 +95,8 @@
 //! ## Options
 //! Prefixed by either `-` or `--` followed by a value, examples: `mycli --my-option=myvalue`,
 //! `mycli -f myvalue`. An option value assignment operator can either be `=` or ` `.
-//! They support the same types as arguments.  
+//! They support the same types as arguments. Their names are matched exactly, `--color` does not
+//! match `--colorize`, unless abbreviations are allowed through the annotation `allow_abbrev`.
 //!
 //! It is possible to have repeating options, example: `mycli -f one -f=two -f three`,
 //! in this case their struct field type is `Vec<T>` with a valid type.
@@ -98,17 +105,39 @@
 //! It is possible to have an option be required through the annotation `required_if`,
 //! for more details see [kurisu_derive](../kurisu_derive/index.html).
 //!
+//! ## Exit codes
+//! [ExitCode](enum.ExitCode.html) follows the `sysexits.h` conventions and implements
+//! `std::process::Termination`, so it can be returned straight from `main`. A `std::io::Error`
+//! converts into its closest exit code, example: `NotFound` becomes `ExitCode::NOINPUT`.
+//! ```
+//! use kurisu::ExitCode;
+//!
+//! fn main() -> ExitCode {
+//!     match std::fs::read_to_string("Cargo.toml") {
+//!         Ok(_) => ExitCode::OK,
+//!         Err(error) => ExitCode::from(&error),
+//!     }
+//! }
+//! ```
+//!
 
 #![forbid(unsafe_code)]
 
 pub mod arg;
+mod env;
 mod exit_code;
+mod lex;
 pub mod mayuri;
 
 pub use arg::Arg;
-use arg::{Parser, VALUE_SEPARATOR};
+use arg::{Parser, Precedence, Repeat};
+pub use env::Env;
 pub use exit_code::*;
+pub use lex::{lex, Token, TokenKind};
 pub use once_cell::sync::OnceCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
 use std::sync::Mutex;
 
 // TODO: Simplify namespaces where we can (outside macro output)
@@ -121,6 +150,74 @@ pub use kurisu_derive::*;
 pub trait Kurisu {
     fn from_args(env_args: Vec<String>) -> Self;
     fn get_info_instance(env_args: Vec<String>) -> &'static Mutex<Info<'static>>;
+    /// Like `get_info_instance`, with the given environment variables instead of capturing the process' ones
+    #[doc(hidden)]
+    fn get_info_instance_env(env_args: Vec<String>, env: Option<Env>) -> &'static Mutex<Info<'static>>;
+    /// Builds the struct from an already parsed `Info`, used by `#[kurisu(flatten)]`
+    #[doc(hidden)]
+    fn from_info(info: &Info<'_>) -> Self;
+    /// The struct's own arguments, without the builtin `--help` & `--version`
+    #[doc(hidden)]
+    fn get_args() -> Vec<Arg<'static>>;
+    /// Fields `default` values that cannot be parsed into their field type
+    #[doc(hidden)]
+    fn get_default_errors() -> Vec<String>;
+    /// First command line value rejected by the `Parser` implementation of its field type, by its `FromStr` one
+    /// for `#[kurisu(from_str)]`, or by its `parse_with` callback
+    #[doc(hidden)]
+    fn get_value_error(_info: &Info<'static>) -> Option<Error> {
+        None
+    }
+
+    /// Like `from_args`, with the given environment variables instead of the process' ones, example: in tests
+    /// ```
+    /// # use kurisu::*;
+    /// # use std::collections::HashMap;
+    /// #[derive(Kurisu)]
+    /// struct Yargs {
+    ///     #[kurisu(env = "DB_HOST")]
+    ///     host: String,
+    /// }
+    ///
+    /// let env: HashMap<String, String> = vec![("DB_HOST".to_string(), "localhost".to_string())].into_iter().collect();
+    /// let yargs = Yargs::from_args_env(Vec::new(), env);
+    /// assert_eq!(yargs.host, "localhost");
+    /// ```
+    fn from_args_env(env_args: Vec<String>, env: impl Into<Env>) -> Self
+    where
+        Self: Sized,
+    {
+        let info = Self::get_info_instance_env(env_args, Some(env.into())).lock().unwrap();
+        exit_args(&info, |code| std::process::exit(code));
+        Self::from_info(&info)
+    }
+
+    /// Panics with a report of every schema error the derive macro cannot detect at compile time,
+    /// such as a `default` value that does not parse into its field type. Meant to be called from a test:
+    /// ```
+    /// # use kurisu::*;
+    /// #[derive(Kurisu)]
+    /// struct Yargs {
+    ///     #[kurisu(default = "8")]
+    ///     knots: usize,
+    /// }
+    ///
+    /// // Within a #[test] function
+    /// Yargs::assert_schema();
+    /// ```
+    fn assert_schema() {
+        let mut kurisu_args = get_builtin_args();
+        kurisu_args.extend(Self::get_args());
+        let mut errors = get_schema_errors(&kurisu_args);
+        errors.extend(Self::get_default_errors());
+        if !errors.is_empty() {
+            panic!(
+                "kurisu: invalid schema for {}\n  - {}",
+                std::any::type_name::<Self>(),
+                errors.join("\n  - ")
+            );
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -130,14 +227,38 @@ pub struct Info<'a> {
     pub desc: Option<&'a str>,
     pub doc: Option<&'a str>,
     pub allow_noargs: bool,
+    pub allow_unknown: bool,
+    pub allow_abbrev: bool,
+    pub precedence: Precedence,
+    /// Migration switch to the behavior prior to `env` being opt-in, see `#[kurisu(implicit_env)]`
+    pub implicit_env: bool,
+    /// Environment variables, captured once when parsing
+    pub env: Env,
+    /// Command line arguments, as given
     pub env_args: Vec<String>,
     pub args: Vec<Arg<'a>>,
+    /// Typed tokens of `env_args`, lexed once when parsing
+    pub tokens: Vec<Token<'a>>,
+    /// Position of each argument within `args` by name, built once when parsing
+    pub names: HashMap<&'a str, usize>,
 }
 impl<'a> Info<'a> {
+    /// Argument of the given field name, looked up through `names` unless `args` changed since
+    pub fn get_arg(&self, name: &str) -> Option<&Arg<'a>> {
+        match self.names.get(name).and_then(|i| self.args.get(*i)) {
+            Some(arg) if arg.name == name => Some(arg),
+            _ => self.args.iter().find(|a| a.name == name),
+        }
+    }
+
     pub fn get_positional_args(&'a self) -> Vec<&'a Arg<'a>> {
         self.args.iter().filter(|a| a.position.is_some()).collect()
     }
 
+    pub fn get_trailing_arg(&'a self) -> Option<&'a Arg<'a>> {
+        self.args.iter().find(|a| a.trailing)
+    }
+
     pub fn get_flags(&'a self) -> Vec<&'a Arg<'a>> {
         self.args
             .iter()
@@ -145,6 +266,11 @@ impl<'a> Info<'a> {
             .collect()
     }
 
+    /// Arguments reading an environment variable, through `env` or `env_prefix`
+    pub fn get_env_args(&'a self) -> Vec<&'a Arg<'a>> {
+        self.args.iter().filter(|a| !a.get_env_names().is_empty()).collect()
+    }
+
     pub fn get_options(&'a self) -> Vec<&'a Arg<'a>> {
         self.args
             .iter()
@@ -180,102 +306,282 @@ where
     None
 }
 
-pub fn normalize_env_args<'a>(args: &[String], kurisu_args: &[Arg<'a>]) -> Vec<String> {
-    let mut env_vars: Vec<String> = Vec::new();
-    let mut previous_flag: String = String::from("");
-    let mut options_ended = false;
+/// Arguments every struct has, `-h --help` and `-V --version`
+#[doc(hidden)]
+pub fn get_builtin_args() -> Vec<Arg<'static>> {
+    vec![
+        Arg {
+            name: "usage",
+            value_type: "bool",
+            short: Some("h"),
+            long: Some("help"),
+            doc: Some("Prints this message"),
+            default: "false",
+            ..Default::default()
+        },
+        Arg {
+            name: "version",
+            value_type: "bool",
+            short: Some("V"),
+            long: Some("version"),
+            doc: Some("Prints version information"),
+            default: "false",
+            ..Default::default()
+        },
+    ]
+}
+
+/// Schema errors of the combined arguments that can only be detected at runtime
+#[doc(hidden)]
+pub fn get_schema_errors(args: &[Arg<'_>]) -> Vec<String> {
+    let mut errors = get_collision_errors(args);
+
+    let singles: Vec<(&str, Vec<&str>)> = vec![
+        ("trailing", args.iter().filter(|a| a.trailing).map(|a| a.name).collect()),
+        ("unknown", args.iter().filter(|a| a.unknown).map(|a| a.name).collect()),
+        ("numeric_shorthand", args.iter().filter(|a| a.numeric_shorthand).map(|a| a.name).collect()),
+    ];
+    for (annotation, names) in singles.iter().filter(|(_, names)| names.len() > 1) {
+        errors.push(format!("`{}` is used on more than one field: {}", annotation, names.join(", ")));
+    }
+
+    // An infinite positional argument absorbs the gaps between positions
+    let has_infinite = args.iter().any(|a| a.position == Some(0));
+    let mut positions: Vec<(i8, &str)> = args
+        .iter()
+        .filter_map(|a| a.position.map(|p| (p, a.name)))
+        .filter(|(p, _)| *p != 0)
+        .collect();
+    positions.sort_unstable();
+    for (i, (pos, name)) in positions.iter().enumerate() {
+        if let Some((_, other)) = positions.iter().take(i).find(|(p, _)| p == pos) {
+            errors.push(format!("position {} of `{}` already bound to `{}`", pos, name, other));
+        } else if !has_infinite && *pos > 1 && !positions.iter().any(|(p, _)| *p == pos - 1) {
+            errors.push(format!("position {} of `{}` but position {} is missing", pos, name, pos - 1));
+        }
+    }
+
+    for arg in args {
+        if let Some(required_if) = arg.required_if {
+            if !args.iter().any(|a| a.name == required_if) {
+                errors.push(format!("required_if of `{}` references unknown field `{}`", arg.name, required_if));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Arguments sharing the same name, short or long flag, the derive macro can only detect these
+/// within a single struct, combining structs with `#[kurisu(flatten)]` is checked here
+fn get_collision_errors(args: &[Arg<'_>]) -> Vec<String> {
+    let mut errors: Vec<String> = Vec::new();
+    let mut names: HashMap<&str, &str> = HashMap::new();
+    let mut shorts: HashMap<&str, &str> = HashMap::new();
+    let mut longs: HashMap<&str, &str> = HashMap::new();
     for arg in args {
-        if arg.len() == 2 && arg == "--" {
-            options_ended = true;
+        if names.insert(arg.name, arg.name).is_some() {
+            errors.push(format!("field `{}` is declared more than once", arg.name));
         }
 
-        let mut arguments: Vec<String> = vec![arg.clone()];
-
-        // check if this is a negative number
-        if !options_ended
-            && arg.starts_with('-')
-            && arg.len() > 2
-            && arg.parse::<isize>().is_err()
-            && !arg.contains(',')
-            && !arg.contains('=')
-            && !arg.starts_with("--")
-        {
-            arguments = Vec::new();
-            let mut unknown_flag = false;
-            let mut value = String::from("");
-            // Normalize short flag value with no spaces `-iVALUE` as well as stacking short flags
-            for short in arg.chars().skip(1) {
-                if !unknown_flag && kurisu_args.iter().filter_map(|a| a.short).any(|x| x == short.to_string().as_str()) {
-                    arguments.push(format!("-{}", short));
-                } else {
-                    unknown_flag = true;
-                    value.push(short);
-                }
+        for short in arg.get_shorts() {
+            let other = shorts.entry(short).or_insert(arg.name);
+            if *other != arg.name {
+                errors.push(format!("short flag -{} of `{}` already bound to `{}`", short, arg.name, other));
             }
+        }
 
-            if !value.is_empty() {
-                arguments.push(value);
+        for long in arg.get_longs() {
+            let other = longs.entry(long).or_insert(arg.name);
+            if *other != arg.name {
+                errors.push(format!("long flag --{} of `{}` already bound to `{}`", long, arg.name, other));
             }
         }
+    }
+
+    errors
+}
+
+/// Long flags of every argument the given `--prefix` could be an abbreviation of
+pub(crate) fn get_abbreviation_candidates<'a>(name: &str, kurisu_args: &[Arg<'a>]) -> Vec<&'a str> {
+    let prefix = name.trim_start_matches('-');
+    kurisu_args
+        .iter()
+        .filter(|a| a.get_longs().any(|l| l.starts_with(prefix)))
+        .filter_map(|a| a.long)
+        .collect()
+}
 
-        for arg in arguments {
-            let karg = &kurisu_args.iter().find(|a| a == &arg);
-            let previous_karg = &kurisu_args.iter().find(|a| a == &previous_flag);
-            if previous_flag.is_empty() && karg.is_none() {
-                env_vars.push(arg.clone());
+/// Command line arguments with one flag or option per argument and their values assigned with `=`,
+/// example: `-vi file.txt` becomes `-v -i=file.txt`. Unknown flags and options are kept as typed.
+pub fn normalize_env_args(args: &[String], kurisu_args: &[Arg<'_>]) -> Vec<String> {
+    let tokens = lex::tokenize(args, kurisu_args, false);
+    let mut env_args: Vec<String> = Vec::new();
+    for (token, values) in lex::group(&tokens) {
+        let karg = token.name.and_then(|name| kurisu_args.iter().find(|a| a.name == name));
+        let karg = match (token.kind, karg) {
+            (TokenKind::Long, Some(karg)) | (TokenKind::Short, Some(karg)) => karg,
+            (TokenKind::Long, None) | (TokenKind::Short, None) => {
+                env_args.push(token.unknown_text(args));
+                continue;
+            }
+            _ => {
+                env_args.push(token.text(args).to_string());
                 continue;
             }
+        };
 
-            // Check for negative numbers
-            // FIXME: Known issue if the env_ars contains `"-m", "-4,-5" for a multiple values it is not normalized correctly. See test: normalize_env_args.multiple_comma_values
-            if arg.parse::<isize>().is_err() && arg.len() > 1 && (arg.starts_with('-') || arg.starts_with("--")) {
-                // Two flags following each other
-                if !previous_flag.is_empty() {
-                    env_vars.push(previous_flag.clone());
-                    previous_flag = String::from("");
-                }
+        // The numeric shorthand `-NUM` only has its dash as a flag
+        let flag = match token.text(args).trim_start_matches('-') {
+            "" => karg.get_flag_name().unwrap_or_default(),
+            name if token.kind == TokenKind::Long => format!("--{}", name),
+            name => format!("-{}", name),
+        };
 
-                if karg.is_some() && karg.expect("Infallible").is_value_none() || arg.contains('=') {
-                    // If we have a comma delimited value and karg support multiple values we split it into multiple args
-                    if karg.expect("Infallible").is_value_multiple() && arg.contains(',') {
-                        let name: Vec<&str> = arg.split('=').collect();
-                        for value in name[1].split(',') {
-                            env_vars.push(format!("{}={}", name[0], value));
-                        }
-                    } else {
-                        env_vars.push(arg.clone());
-                    }
-                    continue;
-                }
+        for value in values {
+            env_args.extend(karg.split_value(value.text(args)).into_iter().map(|v| format!("{}={}", flag, v)));
+        }
 
-                previous_flag = arg.clone();
-                continue;
+        // Not enough values, the bare flag is kept
+        if values.is_empty() || values.len() < lex::get_value_count(karg) {
+            env_args.push(flag);
+        }
+    }
+
+    env_args
+}
+
+/// Whether the argument is a number, or a delimited list of numbers for the given option, rather than a flag, example: `-4,-5`
+pub(crate) fn is_number(arg: &str, karg: Option<&Arg<'_>>) -> bool {
+    let is_number = |value: &str| {
+        let digits = value.strip_prefix('-').unwrap_or(value);
+        digits.starts_with(|c: char| c.is_ascii_digit() || c == '.') && value.parse::<f64>().is_ok()
+    };
+
+    is_number(arg) || karg.and_then(|a| a.get_delimiter()).filter(|d| arg.split(d).all(is_number)).is_some()
+}
+
+/// Lexes `info.env_args` into `info.tokens` and assigns their values to each argument, in a single pass over them
+#[doc(hidden)]
+pub fn set_values(info: &mut Info<'_>) {
+    info.tokens = lex(&info.env_args, info);
+    info.names = info.args.iter().enumerate().map(|(i, a)| (a.name, i)).collect();
+    let Info {
+        args: kurisu_args,
+        env_args,
+        tokens,
+        names,
+        ..
+    } = info;
+
+    // With a trailing argument everything after the first -- belongs to it instead of the positional arguments
+    let has_trailing = kurisu_args.iter().any(|a| a.trailing);
+    let separator = tokens.iter().position(|t| t.kind == TokenKind::Separator).unwrap_or(tokens.len());
+    let is_trailing = |i: usize| has_trailing && i > separator;
+
+    // Arguments by the kind of positional values they take
+    let mut positionals: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut infinites: Vec<usize> = Vec::new();
+    let mut lasts: Vec<usize> = Vec::new();
+    for (i, arg) in kurisu_args.iter().enumerate() {
+        match arg.position {
+            Some(0) => infinites.push(i),
+            Some(-1) => lasts.push(i),
+            Some(position) if position > 0 => positionals.entry(position as usize).or_default().push(i),
+            _ => {}
+        }
+    }
+
+    // Token of the last positional value, for the argument at the last position
+    let last_positional = tokens
+        .iter()
+        .enumerate()
+        .rposition(|(i, t)| t.kind == TokenKind::Positional && !is_trailing(i));
+
+    let mut unknowns: Vec<String> = Vec::new();
+    let mut trailing: Vec<String> = Vec::new();
+    let mut pos = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        match (token.kind, token.name.and_then(|name| names.get(name))) {
+            (TokenKind::Long, Some(&karg)) | (TokenKind::Short, Some(&karg)) => {
+                let arg = &mut kurisu_args[karg];
+                let values = tokens[i + 1..].iter().take_while(|t| t.kind == TokenKind::Value);
+                let values: Vec<String> = values.flat_map(|v| arg.split_value(v.text(env_args))).collect();
+                arg.add_occurrence(&values);
             }
+            // Flags & options that are not defined, as typed, for the catch-all argument
+            (TokenKind::Long, None) | (TokenKind::Short, None) => unknowns.push(token.unknown_text(env_args)),
+            (TokenKind::Positional, _) if is_trailing(i) => trailing.push(token.text(env_args).to_string()),
+            (TokenKind::Positional, _) => {
+                pos += 1;
+                let mut targets: Vec<usize> = positionals.get(&pos).cloned().unwrap_or_default();
+
+                // A value taken by its position is not also the last one, example: `cp only` leaves `DEST` missing
+                if targets.is_empty() && Some(i) == last_positional {
+                    targets.extend(&lasts);
+                }
 
-            // If we have a comma delimited value and karg support multiple values we split it into multiple args
-            if previous_karg.is_some() && previous_karg.expect("Infallible").is_value_multiple() && arg.contains(',') {
-                for value in arg.split(',') {
-                    env_vars.push(format!("{}={}", previous_flag, value));
+                // The infinite argument takes the values no other argument has the position of
+                if targets.is_empty() {
+                    targets.extend(&infinites);
+                }
+
+                for karg in targets {
+                    kurisu_args[karg].add_positional(token.text(env_args));
                 }
-            } else {
-                env_vars.push(format!("{}={}", previous_flag, arg));
             }
+            _ => {}
+        }
+    }
 
-            previous_flag = String::from("");
+    for arg in kurisu_args.iter_mut() {
+        if arg.unknown {
+            arg.occurrences = unknowns.len();
+            arg.value = unknowns.clone();
+        } else if arg.trailing {
+            arg.occurrences = trailing.len();
+            arg.value = trailing.clone();
+        } else if info.implicit_env {
+            arg.set_implicit_fallback_value(&info.env);
+        } else {
+            arg.set_fallback_value(info.precedence, &info.env);
         }
     }
+}
 
-    if !previous_flag.is_empty() {
-        env_vars.push(previous_flag);
+pub fn parse_value<P: Parser>(name: &str, info: &'_ Info) -> P {
+    let arg = info.get_arg(name).expect("Infallible");
+    P::parse(arg.get_values())
+}
+
+/// Usage error naming the argument whose values are rejected by the `Parser` implementation of its type,
+/// example: `--rgb 1,2` for a `[u8; 3]`
+pub fn validate_value<P: Parser>(name: &str, info: &Info<'static>) -> Option<Error> {
+    let arg = info.get_arg(name).expect("Infallible");
+    let values = arg.get_values();
+    if values.is_empty() {
+        return None;
     }
 
-    env_vars
+    let error = P::validate(values).err()?;
+    Some(Error::invalid_value(arg, &values.join(" "), error))
 }
 
-pub fn parse_value<P: Parser>(name: &str, info: &'_ Info) -> P {
-    let arg = info.args.iter().find(|a| name == a.name).expect("Infallible");
-    let value = arg.value.join(VALUE_SEPARATOR);
-    P::parse(value.as_str())
+/// Parses the value of an argument with the `FromStr` implementation of its type, its default value if missing or invalid
+pub fn parse_from_str<T: FromStr + Default>(name: &str, info: &'_ Info) -> T {
+    let arg = info.get_arg(name).expect("Infallible");
+    arg.get_values().last().and_then(|v| v.parse::<T>().ok()).unwrap_or_default()
+}
+
+/// Usage error naming the argument whose value is rejected by the `FromStr` implementation of its type
+pub fn validate_from_str<T: FromStr>(name: &str, info: &Info<'static>) -> Option<Error>
+where
+    T::Err: Display,
+{
+    let arg = info.get_arg(name).expect("Infallible");
+    let value = arg.get_values().last()?;
+    let error = T::from_str(value).err()?;
+    Some(Error::invalid_value(arg, value, error))
 }
 
 pub fn valid_exit<T: Kurisu>(kurisu_struct: &T) {
@@ -292,19 +598,40 @@ pub fn validate_usage<T: Kurisu>(_kurisu_struct: &T) -> Option<Error> {
     }
 
     let positions: Vec<i8> = info.args.iter().filter_map(|a| a.position).collect();
+    let has_trailing = info.args.iter().any(|a| a.trailing);
+    let allow_unknown = info.allow_unknown || info.args.iter().any(|a| a.unknown);
+    let tokens = lex::group(&info.tokens);
 
     // Always validate invalid options & args first
-    let mut pos: i8 = 0;
-    for arg in info.env_args.as_slice() {
-        if arg.starts_with('-') {
-            if !info.args.iter().any(|a| &a == arg) {
-                return Some(Error::Invalid(arg.clone()));
+    let mut positionals: Vec<&str> = Vec::new();
+    for (token, _) in tokens.iter() {
+        match (token.kind, token.name) {
+            (TokenKind::Separator, _) if has_trailing => break,
+            (TokenKind::Long, None) | (TokenKind::Short, None) => {
+                let arg = token.unknown_text(&info.env_args);
+                if info.allow_abbrev && token.kind == TokenKind::Long {
+                    let candidates = get_abbreviation_candidates(token.text(&info.env_args), &info.args);
+                    if candidates.len() > 1 {
+                        return Some(Error::Ambiguous(arg, candidates.iter().map(|c| format!("--{}", c)).collect()));
+                    }
+                }
+
+                if !allow_unknown {
+                    return Some(Error::Invalid(arg));
+                }
             }
-        } else {
-            pos += 1;
-            // If we have an infinite positional args we can never get an invalid pos
-            if !positions.contains(&0) && !positions.contains(&pos) {
-                return Some(Error::Invalid(arg.clone()));
+            (TokenKind::Positional, _) => positionals.push(token.text(&info.env_args)),
+            _ => {}
+        }
+    }
+
+    // If we have an infinite positional args we can never get an invalid pos
+    if !positions.contains(&0) {
+        for (i, arg) in positionals.iter().enumerate() {
+            let pos = (i + 1) as i8;
+            let is_last = positions.contains(&-1) && i + 1 == positionals.len();
+            if !positions.contains(&pos) && !is_last {
+                return Some(Error::Invalid(arg.to_string()));
             }
         }
     }
@@ -324,6 +651,20 @@ pub fn validate_usage<T: Kurisu>(_kurisu_struct: &T) -> Option<Error> {
         }
     }
 
+    // Options not followed by as many values as they take, example: `--rename OLD` with `num_values = 2`
+    for (token, values) in tokens.iter() {
+        let arg = token.name.and_then(|name| info.get_arg(name));
+        if let Some(arg) = arg.filter(|a| values.len() < lex::get_value_count(a)) {
+            return Some(Error::RequiresValue(arg.clone()));
+        }
+    }
+
+    for arg in info.args.iter().filter(|a| a.repeat == Repeat::Error) {
+        if arg.is_value_repeated() {
+            return Some(Error::Repeated(arg.clone()));
+        }
+    }
+
     for arg in info.args.iter().filter(|a| a.required_if.is_some()) {
         let counter_part = info.args.iter().find(|a| a.name == arg.required_if.expect("Infallible"));
         if let Some(counter_part) = counter_part {
@@ -333,6 +674,10 @@ pub fn validate_usage<T: Kurisu>(_kurisu_struct: &T) -> Option<Error> {
         }
     }
 
+    if let Some(error) = T::get_value_error(&info) {
+        return Some(error);
+    }
+
     // TODO: Validate arg type range, such as usize that cant be negative, etc...
 
     None
@@ -1,6 +1,6 @@
 use crate::arg::Error;
+use crate::arg::Message;
 use crate::{Arg, ExitCode, Info, Kurisu};
-use std::cmp::Ordering;
 use textwrap::Wrapper;
 
 // TODO: Add test for display layer...
@@ -10,55 +10,36 @@ const ARG_INDENT: &str = "    ";
 pub fn print_usage_error<T: Kurisu>(_kurisu_struct: &T, arg_error: Option<Error>) {
     if let Some(error) = arg_error {
         let info = T::get_info_instance(std::env::args().skip(1).collect()).lock().unwrap();
-        let exit_code = match error {
+        match error {
             Error::NoArgs => print_help(&info),
-            Error::Invalid(arg) => print_invalid_arg(arg, &info),
-            Error::RequiresPositional(arg) => print_missing_positional(arg, &info),
-            Error::RequiresValue(arg) => print_missing_value(arg, &info),
-            Error::RequiresValueIf(a, _b) => print_missing_value(a, &info),
-            Error::Custom(text) => print_custom_error(text, &info),
-            Error::CustomArg(arg, text) => print_custom_arg_error(arg, text, &info),
+            ref error => print_error(error.to_string(), &info),
         };
 
-        std::process::exit(exit_code);
+        std::process::exit(error.exit_code().into());
     }
 }
 
 pub fn print_custom_error(text: String, info: &Info) -> i32 {
-    print_error_text(info, text);
-    print_usage(info);
-    print_more_info();
-    ExitCode::USAGE.into()
+    print_error(Message::Custom(&text).to_string(), info)
 }
 
 pub fn print_custom_arg_error(arg: Arg, text: String, info: &Info) -> i32 {
-    let arg_string = format!("{}", arg);
-    print_error_text(info, format!("{} {}", arg_string.trim(), text));
-    print_usage(info);
-    print_more_info();
-    ExitCode::USAGE.into()
+    print_error(Message::CustomArg(&arg, &text).to_string(), info)
 }
 
 pub fn print_invalid_arg(arg: String, info: &Info) -> i32 {
-    let error = format!("unrecognized option {}", arg);
-    print_error_text(info, error);
-    print_usage(info);
-    print_more_info();
-    ExitCode::USAGE.into()
+    print_error(Message::Invalid(&arg).to_string(), info)
 }
 
 pub fn print_missing_positional(arg: Arg, info: &Info) -> i32 {
-    let arg_string = format!("{}", arg);
-    let error = format!("missing argument {}", arg_string.trim());
-    print_error_text(info, error);
-    print_usage(info);
-    print_more_info();
-    ExitCode::USAGE.into()
+    print_error(Message::RequiresPositional(&arg).to_string(), info)
 }
 
 pub fn print_missing_value(arg: Arg, info: &Info) -> i32 {
-    let arg_string = format!("{}", arg);
-    let error = format!("missing value for option {}", arg_string.trim());
+    print_error(Message::RequiresValue(&arg).to_string(), info)
+}
+
+fn print_error(error: String, info: &Info) -> i32 {
     print_error_text(info, error);
     print_usage(info);
     print_more_info();
@@ -81,9 +62,12 @@ pub fn print_help(info: &Info) -> i32 {
 
     print_usage(info);
 
-    let args: Vec<&Arg> = info.get_positional_args();
+    let mut args: Vec<&Arg> = info.get_positional_args();
     let flags: Vec<&Arg> = info.get_flags();
     let options: Vec<&Arg> = info.get_options();
+    if let Some(trailing) = info.get_trailing_arg() {
+        args.push(trailing);
+    }
 
     if !flags.is_empty() {
         println!();
@@ -109,6 +93,15 @@ pub fn print_help(info: &Info) -> i32 {
         }
     }
 
+    let env_args = info.get_env_args();
+    if !env_args.is_empty() {
+        println!();
+        println!("ENVIRONMENT:");
+        for line in get_env_usage_lines(env_args) {
+            println!("{}", line);
+        }
+    }
+
     // println!();
     // println!("SUBCOMMANDS:");
 
@@ -147,13 +140,11 @@ fn print_usage(info: &Info) {
             }
         }
 
-        position_args.sort_by(|a, b| {
-            // Infinite argument always goes last
-            if a.0 == 0 {
-                return Ordering::Greater;
-            }
-
-            a.0.cmp(&b.0)
+        // Infinite argument goes after the numbered positions, only the last position goes after it
+        position_args.sort_by_key(|a| match a.0 {
+            0 => i8::MAX - 1,
+            -1 => i8::MAX,
+            pos => pos,
         });
         let ordered_args: Vec<&str> = position_args.iter().map(|a| a.1.trim()).collect();
         format!(" {}", ordered_args.join(" "))
@@ -173,11 +164,38 @@ fn print_usage(info: &Info) {
         String::from(" [FLAGS]")
     };
 
+    let usage_trailing = if let Some(trailing) = info.get_trailing_arg() {
+        format!(" [-- {}]", format!("{}", trailing).trim())
+    } else {
+        String::from("")
+    };
+
     let bin_name = info.name.unwrap_or("unknown");
 
     println!();
     println!("USAGE:");
-    println!("{}{}{}{}", ARG_INDENT, bin_name, usage_options, usage_args);
+    println!("{}{}{}{}{}", ARG_INDENT, bin_name, usage_options, usage_args, usage_trailing);
+}
+
+fn get_env_usage_lines(args: Vec<&Arg>) -> Vec<String> {
+    let env_names: Vec<(String, &Arg)> = args
+        .into_iter()
+        .flat_map(|a| a.get_env_names().into_iter().map(move |n| (n, a)))
+        .collect();
+    let column1_width = env_names.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    env_names
+        .iter()
+        .map(|(name, arg)| {
+            format!(
+                "{}{:width$}{}{}",
+                ARG_INDENT,
+                name,
+                DESC_SPACER,
+                arg.to_string().trim(),
+                width = column1_width
+            )
+        })
+        .collect()
 }
 
 fn get_arg_usage_lines(args: Vec<&Arg>, term_width: usize) -> Vec<String> {
@@ -191,11 +209,15 @@ fn get_arg_usage_lines(args: Vec<&Arg>, term_width: usize) -> Vec<String> {
             String::from("")
         };
 
-        let default = if !arg.default.is_empty() && !arg.is_value_none() {
-            format!(" [default: {}]", arg.default)
-        } else {
-            String::from("")
-        };
+        let mut default = String::from("");
+        let env_names = arg.get_env_names();
+        if !env_names.is_empty() {
+            default = format!(" [env: {}]", env_names.join(", "));
+        }
+
+        if !arg.default.is_empty() && !arg.is_value_none() {
+            default = format!("{} [default: {}]", default, arg.default);
+        }
 
         let arg_string = format!("{}{}", ARG_INDENT, arg);
         let extra_lines_indent = String::from(" ").repeat(DESC_SPACER.len() + column1_width + 2);
@@ -205,7 +227,7 @@ fn get_arg_usage_lines(args: Vec<&Arg>, term_width: usize) -> Vec<String> {
             .join("\n");
 
         line = line.trim_matches('\n').to_string();
-        if arg.position.is_some() {
+        if arg.position.is_some() || arg.trailing {
             line = format!("{}{}", ARG_INDENT, line.trim());
         }
         lines.push(line);
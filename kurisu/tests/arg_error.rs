@@ -0,0 +1,76 @@
+use kurisu::arg::Error;
+use kurisu::*;
+
+fn vec_to_string(args: Vec<&str>) -> Vec<String> {
+    let mut strings = Vec::new();
+    for arg in args {
+        strings.push(arg.to_string());
+    }
+
+    strings
+}
+
+#[test]
+fn display() {
+    let arg = Arg {
+        name: "test",
+        value_type: "String",
+        short: Some("t"),
+        long: Some("test"),
+        ..Default::default()
+    };
+
+    assert_eq!(Error::Custom(String::from("went wrong")).to_string(), "went wrong");
+    assert_eq!(
+        Error::CustomArg(arg.clone(), String::from("went wrong")).to_string(),
+        "-t --test <TEST> went wrong"
+    );
+    assert_eq!(Error::NoArgs.to_string(), "missing arguments");
+    assert_eq!(Error::Invalid(String::from("--nope")).to_string(), "unrecognized option --nope");
+    assert_eq!(
+        Error::Ambiguous(String::from("--col"), vec_to_string(vec!["--color", "--colormap"])).to_string(),
+        "ambiguous option --col could match --color, --colormap"
+    );
+    assert_eq!(Error::RequiresPositional(arg.clone()).to_string(), "missing argument -t --test <TEST>");
+    assert_eq!(Error::RequiresValue(arg.clone()).to_string(), "missing value for option -t --test <TEST>");
+    assert_eq!(
+        Error::Repeated(arg.clone()).to_string(),
+        "option -t --test <TEST> cannot be used multiple times"
+    );
+}
+
+#[test]
+fn display_required_if() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short)]
+        atest: String,
+        #[kurisu(short, required_if = "atest")]
+        btest: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-a=test"]));
+    let error = kurisu::validate_usage(&yargs).unwrap();
+    assert_eq!(error.to_string(), "missing value for option -a --atest <ATEST>");
+}
+
+#[test]
+fn exit_code() {
+    let error = Error::Invalid(String::from("--nope"));
+    assert_eq!(error.exit_code(), ExitCode::USAGE);
+    assert_eq!(ExitCode::from(&error), ExitCode::USAGE);
+    assert_eq!(i32::from(error), 64);
+}
+
+#[test]
+fn std_error() {
+    fn run() -> Result<(), Box<dyn std::error::Error>> {
+        Err(Error::Invalid(String::from("-k")))?;
+        Ok(())
+    }
+
+    let error = run().unwrap_err();
+    assert_eq!(error.to_string(), "unrecognized option -k");
+    assert!(error.downcast_ref::<Error>().is_some());
+}
@@ -0,0 +1,39 @@
+use kurisu::ExitCode;
+use std::io::{Error, ErrorKind};
+
+/// `std::process::ExitCode` has no `PartialEq` on Rust 1.61, compare through its debug output instead
+fn assert_same(left: std::process::ExitCode, right: std::process::ExitCode) {
+    assert_eq!(format!("{:?}", left), format!("{:?}", right));
+}
+
+#[test]
+fn into_i32() {
+    assert_eq!(i32::from(ExitCode::OK), 0);
+    assert_eq!(i32::from(ExitCode::USAGE), 64);
+    assert_eq!(i32::from(ExitCode::CONFIG), 78);
+}
+
+#[test]
+fn into_process_exit_code() {
+    assert_same(std::process::ExitCode::from(ExitCode::OK), std::process::ExitCode::SUCCESS);
+    assert_same(std::process::ExitCode::from(ExitCode::NOPERM), std::process::ExitCode::from(77));
+}
+
+#[test]
+fn termination() {
+    use std::process::Termination;
+
+    assert_same(ExitCode::FAILURE.report(), std::process::ExitCode::FAILURE);
+    assert_same(ExitCode::NOINPUT.report(), std::process::ExitCode::from(66));
+}
+
+#[test]
+fn from_io_error() {
+    assert_eq!(ExitCode::from(&Error::from(ErrorKind::NotFound)), ExitCode::NOINPUT);
+    assert_eq!(ExitCode::from(&Error::from(ErrorKind::PermissionDenied)), ExitCode::NOPERM);
+    assert_eq!(ExitCode::from(&Error::from(ErrorKind::AlreadyExists)), ExitCode::CANTCREAT);
+    assert_eq!(ExitCode::from(&Error::from(ErrorKind::InvalidData)), ExitCode::DATAERR);
+    assert_eq!(ExitCode::from(&Error::from(ErrorKind::ConnectionRefused)), ExitCode::UNAVAILABLE);
+    assert_eq!(ExitCode::from(&Error::from(ErrorKind::TimedOut)), ExitCode::TEMPFAIL);
+    assert_eq!(ExitCode::from(Error::from(ErrorKind::BrokenPipe)), ExitCode::IOERR);
+}
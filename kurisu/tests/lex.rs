@@ -0,0 +1,174 @@
+use kurisu::arg::Precedence;
+use kurisu::*;
+use std::collections::HashMap;
+
+fn vec_to_string(args: Vec<&str>) -> Vec<String> {
+    let mut strings = Vec::new();
+    for arg in args {
+        strings.push(arg.to_string());
+    }
+
+    strings
+}
+
+fn info_with_args(args: Vec<Arg<'static>>) -> Info<'static> {
+    Info {
+        name: None,
+        version: None,
+        desc: None,
+        doc: None,
+        allow_noargs: false,
+        allow_unknown: false,
+        allow_abbrev: true,
+        precedence: Precedence::default(),
+        implicit_env: false,
+        env: Env::default(),
+        env_args: Vec::new(),
+        args,
+        tokens: Vec::new(),
+        names: HashMap::new(),
+    }
+}
+
+fn kurisu_args() -> Vec<Arg<'static>> {
+    vec![
+        Arg {
+            name: "verbose",
+            value_type: "bool",
+            short: Some("v"),
+            long: Some("verbose"),
+            ..Default::default()
+        },
+        Arg {
+            name: "input",
+            value_type: "String",
+            short: Some("i"),
+            long: Some("input"),
+            ..Default::default()
+        },
+        Arg {
+            name: "color",
+            value_type: "String",
+            long: Some("color"),
+            default_missing: Some("always"),
+            ..Default::default()
+        },
+        Arg {
+            name: "size",
+            value_type: "(usize, usize)",
+            long: Some("size"),
+            num_values: Some(2),
+            ..Default::default()
+        },
+        Arg {
+            name: "files",
+            value_type: "Vec<String>",
+            multiple: true,
+            position: Some(0),
+            ..Default::default()
+        },
+    ]
+}
+
+/// Kind, argument index, text and name of every token
+fn describe<'a>(tokens: &[Token<'a>], args: &'a [String]) -> Vec<(TokenKind, usize, &'a str, Option<&'a str>)> {
+    tokens.iter().map(|t| (t.kind, t.index, t.text(args), t.name)).collect()
+}
+
+#[test]
+fn long_options() {
+    let info = info_with_args(kurisu_args());
+    let args = vec_to_string(vec!["--input", "file.txt", "--input=other.txt", "--color", "--color=never", "--verb"]);
+    let tokens = lex(&args, &info);
+    assert_eq!(
+        describe(&tokens, &args),
+        vec![
+            (TokenKind::Long, 0, "--input", Some("input")),
+            (TokenKind::Value, 1, "file.txt", Some("input")),
+            (TokenKind::Long, 2, "--input", Some("input")),
+            (TokenKind::Value, 2, "other.txt", Some("input")),
+            (TokenKind::Long, 3, "--color", Some("color")),
+            (TokenKind::Long, 4, "--color", Some("color")),
+            (TokenKind::Value, 4, "never", Some("color")),
+            (TokenKind::Long, 5, "--verb", Some("verbose")),
+        ]
+    );
+    assert_eq!(tokens[3].range, 8..17);
+}
+
+#[test]
+fn short_flag_stacking() {
+    let info = info_with_args(kurisu_args());
+    let args = vec_to_string(vec!["-vvifile.txt", "-vi=other.txt", "-vx"]);
+    let tokens = lex(&args, &info);
+    assert_eq!(
+        describe(&tokens, &args),
+        vec![
+            (TokenKind::Short, 0, "-v", Some("verbose")),
+            (TokenKind::Short, 0, "v", Some("verbose")),
+            (TokenKind::Short, 0, "i", Some("input")),
+            (TokenKind::Value, 0, "file.txt", Some("input")),
+            (TokenKind::Short, 1, "-v", Some("verbose")),
+            (TokenKind::Short, 1, "i", Some("input")),
+            (TokenKind::Value, 1, "other.txt", Some("input")),
+            (TokenKind::Short, 2, "-v", Some("verbose")),
+            (TokenKind::Short, 2, "x", None),
+        ]
+    );
+    assert_eq!(tokens[2].range, 3..4);
+}
+
+#[test]
+fn positionals_and_separator() {
+    let info = info_with_args(kurisu_args());
+    let args = vec_to_string(vec!["a.txt", "-", "--size", "800", "-600", "--nope", "--", "-v", "--"]);
+    let tokens = lex(&args, &info);
+    assert_eq!(
+        describe(&tokens, &args),
+        vec![
+            (TokenKind::Positional, 0, "a.txt", None),
+            (TokenKind::Positional, 1, "-", None),
+            (TokenKind::Long, 2, "--size", Some("size")),
+            (TokenKind::Value, 3, "800", Some("size")),
+            (TokenKind::Value, 4, "-600", Some("size")),
+            (TokenKind::Long, 5, "--nope", None),
+            (TokenKind::Separator, 6, "--", None),
+            (TokenKind::Positional, 7, "-v", None),
+            (TokenKind::Positional, 8, "--", None),
+        ]
+    );
+}
+
+#[test]
+fn missing_value() {
+    let info = info_with_args(kurisu_args());
+    let args = vec_to_string(vec!["--input", "-v"]);
+    let tokens = lex(&args, &info);
+    assert_eq!(
+        describe(&tokens, &args),
+        vec![
+            (TokenKind::Long, 0, "--input", Some("input")),
+            (TokenKind::Short, 1, "-v", Some("verbose"))
+        ]
+    );
+}
+
+#[test]
+fn parsed_tokens() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short)]
+        verbose: bool,
+        #[kurisu(short)]
+        input: String,
+        #[kurisu(pos)]
+        files: Vec<String>,
+    }
+
+    let args = vec_to_string(vec!["-vifile.txt", "a.txt", "--", "-b.txt"]);
+    let info = Yargs::get_info_instance(args.clone()).lock().unwrap();
+    assert_eq!(lex(&args, &info), info.tokens);
+    assert_eq!(info.get_arg("input").unwrap().value, vec_to_string(vec!["file.txt"]));
+    assert_eq!(info.get_arg("files").unwrap().value, vec_to_string(vec!["a.txt", "-b.txt"]));
+}
@@ -226,12 +226,14 @@ fn multiple_values() {
         Arg {
             name: "test1",
             value_type: "Vec < String >",
+            multiple: true,
             long: Some("mul"),
             ..Default::default()
         },
         Arg {
             name: "test2",
             value_type: "Vec < String >",
+            multiple: true,
             short: Some("m"),
             ..Default::default()
         },
@@ -249,12 +251,14 @@ fn multiple_comma_values() {
         Arg {
             name: "test1",
             value_type: "Vec < String >",
+            multiple: true,
             long: Some("mul"),
             ..Default::default()
         },
         Arg {
             name: "test2",
             value_type: "Vec < isize >",
+            multiple: true,
             short: Some("m"),
             ..Default::default()
         },
@@ -315,6 +319,7 @@ fn only_positional_values_follow() {
         Arg {
             name: "test1",
             value_type: "Vec < String >",
+            multiple: true,
             short: Some("a"),
             position: Some(0),
             ..Default::default()
@@ -335,7 +340,7 @@ fn only_positional_values_follow() {
     ];
 
     let args = vec_to_string(vec!["-c", "--", "-a", "-b", "test", "-d"]);
-    let expected = vec_to_string(vec!["-c", "--", "-a", "-b=test", "-d"]);
+    let expected = vec_to_string(vec!["-c", "--", "-a", "-b", "test", "-d"]);
     let norm_args = normalize_env_args(&args, &kurisu_args);
     assert_eq!(norm_args, expected);
 }
@@ -354,3 +359,167 @@ fn short_flag_no_space_value() {
     let norm_args = normalize_env_args(&args, &kurisu_args);
     assert_eq!(norm_args, expected);
 }
+
+#[test]
+fn unknown_kept_as_typed() {
+    let kurisu_args = vec![
+        Arg {
+            name: "test1",
+            value_type: "bool",
+            short: Some("a"),
+            ..Default::default()
+        },
+        Arg {
+            name: "test2",
+            value_type: "String",
+            long: Some("name"),
+            ..Default::default()
+        },
+    ];
+
+    let args = vec_to_string(vec!["-xyz", "--name", "--other=value", "-ab", "--name", "test"]);
+    let expected = vec_to_string(vec!["-xyz", "--name", "--other=value", "-a", "-b", "--name=test"]);
+    let norm_args = normalize_env_args(&args, &kurisu_args);
+    assert_eq!(norm_args, expected);
+}
+
+#[test]
+fn optional_value_not_joined() {
+    let kurisu_args = vec![Arg {
+        name: "color",
+        value_type: "String",
+        short: Some("c"),
+        long: Some("color"),
+        default_missing: Some("always"),
+        ..Default::default()
+    }];
+
+    let args = vec_to_string(vec!["--color", "never", "-c", "file", "-cauto", "--color=auto"]);
+    let expected = vec_to_string(vec!["--color", "never", "-c", "file", "-c=auto", "--color=auto"]);
+    let norm_args = normalize_env_args(&args, &kurisu_args);
+    assert_eq!(norm_args, expected);
+}
+
+#[test]
+fn num_values_grouped() {
+    let kurisu_args = vec![
+        Arg {
+            name: "rename",
+            value_type: "(String, String)",
+            long: Some("rename"),
+            num_values: Some(2),
+            ..Default::default()
+        },
+        Arg {
+            name: "verbose",
+            value_type: "bool",
+            short: Some("v"),
+            ..Default::default()
+        },
+    ];
+
+    let args = vec_to_string(vec!["--rename", "a", "-1", "file", "--rename=c", "-v", "--rename", "e"]);
+    let expected = vec_to_string(vec![
+        "--rename=a",
+        "--rename=-1",
+        "file",
+        "--rename=c",
+        "--rename",
+        "-v",
+        "--rename=e",
+        "--rename",
+    ]);
+    let norm_args = normalize_env_args(&args, &kurisu_args);
+    assert_eq!(norm_args, expected);
+}
+
+#[test]
+fn allow_hyphen_values() {
+    let kurisu_args = vec![
+        Arg {
+            name: "pattern",
+            value_type: "String",
+            short: Some("p"),
+            long: Some("pattern"),
+            allow_hyphen_values: true,
+            ..Default::default()
+        },
+        Arg {
+            name: "invert",
+            value_type: "bool",
+            short: Some("i"),
+            ..Default::default()
+        },
+    ];
+
+    let args = vec_to_string(vec!["--pattern", "-i", "-p", "--", "-i"]);
+    let expected = vec_to_string(vec!["--pattern=-i", "-p", "--", "-i"]);
+    let norm_args = normalize_env_args(&args, &kurisu_args);
+    assert_eq!(norm_args, expected);
+}
+
+#[test]
+fn multiple_negative_values() {
+    let kurisu_args = vec![Arg {
+        name: "test1",
+        value_type: "Vec < f64 >",
+        multiple: true,
+        short: Some("m"),
+        ..Default::default()
+    }];
+
+    let args = vec_to_string(vec!["-m", "-4,-5", "-m", "-1.5e3"]);
+    let expected = vec_to_string(vec!["-m=-4", "-m=-5", "-m=-1.5e3"]);
+    let norm_args = normalize_env_args(&args, &kurisu_args);
+    assert_eq!(norm_args, expected);
+}
+
+#[test]
+fn delimiter() {
+    let kurisu_args = vec![
+        Arg {
+            name: "include",
+            value_type: "Vec < PathBuf >",
+            multiple: true,
+            long: Some("include"),
+            delimiter: Some(":"),
+            ..Default::default()
+        },
+        Arg {
+            name: "exclude",
+            value_type: "Vec < String >",
+            multiple: true,
+            long: Some("exclude"),
+            ..Default::default()
+        },
+        Arg {
+            name: "pattern",
+            value_type: "Vec < String >",
+            multiple: true,
+            long: Some("pattern"),
+            no_delimiter: true,
+            ..Default::default()
+        },
+        Arg {
+            name: "offset",
+            value_type: "Vec < isize >",
+            multiple: true,
+            short: Some("o"),
+            delimiter: Some(":"),
+            ..Default::default()
+        },
+    ];
+
+    let args = vec_to_string(vec!["--include", "a,b:c", "--exclude=a\\,b.txt,c.txt", "--pattern", "a,b", "-o", "-1:-2"]);
+    let expected = vec_to_string(vec![
+        "--include=a,b",
+        "--include=c",
+        "--exclude=a,b.txt",
+        "--exclude=c.txt",
+        "--pattern=a,b",
+        "-o=-1",
+        "-o=-2",
+    ]);
+    let norm_args = normalize_env_args(&args, &kurisu_args);
+    assert_eq!(norm_args, expected);
+}
@@ -2,7 +2,7 @@
 extern crate float_cmp;
 
 use kurisu::*;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -430,6 +430,7 @@ fn positional_infinite_optional() {
 
 #[test]
 fn positional_infinite_string() {
+    #[allow(dead_code)]
     #[derive(Kurisu)]
     struct Yargs {
         #[kurisu(pos = 1)]
@@ -441,8 +442,21 @@ fn positional_infinite_string() {
     let yargs = Yargs::from_args(vec_to_string(vec!["dmail", "test1", "test2", "test3"]));
 
     assert_eq!(yargs.subcommand, String::from("dmail"));
-    assert!(!yargs.args.contains(kurisu::arg::VALUE_SEPARATOR));
-    assert_eq!(yargs.args, String::from("test1 test2 test3"));
+    let error = kurisu::validate_usage(&yargs).unwrap();
+    assert_eq!(error.to_string(), "argument <ARGS> cannot be given multiple values");
+}
+
+#[test]
+fn positional_infinite_string_last() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos, repeat = "last")]
+        args: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["test1", "test2"]));
+    assert_eq!(yargs.args, String::from("test2"));
+    assert!(kurisu::validate_usage(&yargs).is_none());
 }
 
 #[test]
@@ -500,6 +514,7 @@ fn environment_var_fallback() {
     std::env::set_var("MY_ENV_VAR", "TESTING ENV VARS");
 
     #[derive(Kurisu)]
+    #[kurisu(implicit_env)]
     struct Yargs {
         my_env_var: String,
     }
@@ -509,11 +524,27 @@ fn environment_var_fallback() {
     assert_eq!(yargs.my_env_var, *vars.get("MY_ENV_VAR").unwrap());
 }
 
+#[test]
+fn environment_var_fallback_default() {
+    std::env::set_var("IMPLICIT_LEVEL", "debug");
+
+    #[derive(Kurisu)]
+    #[kurisu(implicit_env)]
+    struct Yargs {
+        #[kurisu(default = "info")]
+        implicit_level: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![]));
+    assert_eq!(yargs.implicit_level, "info");
+}
+
 #[test]
 fn environment_var_fallback_prefix() {
     std::env::set_var("MY_ENV_VAR", "TESTING ENV VARS");
 
     #[derive(Kurisu)]
+    #[kurisu(implicit_env)]
     struct Yargs {
         #[kurisu(env_prefix = "MY_")]
         env_var: String,
@@ -539,6 +570,210 @@ fn environment_var_fallback_override() {
     assert_eq!(yargs.test, *vars.get("MY_ENV_VAR").unwrap());
 }
 
+#[test]
+fn environment_var_opt_in() {
+    std::env::set_var("OPT_IN_SHELL", "/bin/sh");
+    std::env::set_var("OPT_IN_TERM", "xterm");
+    std::env::set_var("OPT_IN_USER", "kurisu");
+
+    #[derive(Kurisu)]
+    struct Yargs {
+        opt_in_shell: String,
+        #[kurisu(env = "opt_in_term")]
+        term: String,
+        #[kurisu(env = "OPT_IN_USER")]
+        user: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![]));
+    assert_eq!(yargs.opt_in_shell, "");
+    assert_eq!(yargs.term, "");
+    assert_eq!(yargs.user, "kurisu");
+}
+
+#[test]
+fn environment_var_struct_prefix() {
+    std::env::set_var("MYAPP_HOST", "db.example.com");
+    std::env::set_var("MYAPP_MAX_COUNT", "12");
+    std::env::set_var("MYAPP_USER", "admin");
+    std::env::set_var("OTHER_PORT", "5432");
+
+    #[derive(Kurisu)]
+    #[kurisu(env_prefix = "MYAPP_")]
+    struct Yargs {
+        host: String,
+        max_count: usize,
+        #[kurisu(noenv)]
+        user: String,
+        #[kurisu(env_prefix = "OTHER_")]
+        port: usize,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![]));
+    assert_eq!(yargs.host, "db.example.com");
+    assert_eq!(yargs.max_count, 12);
+    assert_eq!(yargs.user, "");
+    assert_eq!(yargs.port, 5432);
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    let env_names: Vec<String> = info.get_env_args().iter().flat_map(|a| a.get_env_names()).collect();
+    assert_eq!(env_names, vec_to_string(vec!["MYAPP_HOST", "MYAPP_MAX_COUNT", "OTHER_PORT"]));
+}
+
+#[test]
+fn environment_prefix_command_line_only() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    #[kurisu(env_prefix = "CPX_")]
+    struct Yargs {
+        verbose: bool,
+        #[kurisu(trailing)]
+        rest: Vec<String>,
+        #[kurisu(unknown)]
+        extra: Vec<String>,
+    }
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    let env_names: Vec<String> = info.get_env_args().iter().flat_map(|a| a.get_env_names()).collect();
+    assert_eq!(env_names, vec_to_string(vec!["CPX_VERBOSE"]));
+}
+
+#[test]
+fn environment_var_fallback_names() {
+    std::env::set_var("FALLBACK_DB_URL", "mysql://localhost");
+    std::env::set_var("FALLBACK_NAMES_PORT", "5432");
+    std::env::set_var("FALLBACK_DB_PORT", "3306");
+
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(env = "FALLBACK_DATABASE_URL,FALLBACK_DB_URL")]
+        url: String,
+        #[kurisu(env = "FALLBACK_NAMES_PORT,FALLBACK_DB_PORT")]
+        port: usize,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![]));
+    assert_eq!(yargs.url, "mysql://localhost");
+    assert_eq!(yargs.port, 5432);
+}
+
+#[test]
+fn environment_var_list() {
+    std::env::set_var("LIST_INCLUDE", "a:b\\:c:d");
+    std::env::set_var("LIST_TAGS", "x,y");
+    std::env::set_var("LIST_PORTS", "80;443");
+    std::env::set_var("LIST_EMPTY", "");
+
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(env = "LIST_INCLUDE")]
+        include: Vec<PathBuf>,
+        #[kurisu(env = "LIST_TAGS")]
+        tags: Vec<String>,
+        #[kurisu(env = "LIST_PORTS", env_delimiter = ";")]
+        ports: Vec<usize>,
+        #[kurisu(env = "LIST_EMPTY")]
+        empty: Vec<String>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![]));
+    assert_eq!(yargs.include, vec![PathBuf::from("a"), PathBuf::from("b:c"), PathBuf::from("d")]);
+    assert_eq!(yargs.tags, vec_to_string(vec!["x", "y"]));
+    assert_eq!(yargs.ports, vec![80, 443]);
+    assert!(yargs.empty.is_empty());
+}
+
+#[test]
+fn environment_var_list_qualified_path() {
+    std::env::set_var("QUALIFIED_SEARCH", "c:d");
+
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(env = "QUALIFIED_SEARCH")]
+        search: std::collections::VecDeque<std::path::PathBuf>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![]));
+    assert_eq!(yargs.search, VecDeque::from(vec![PathBuf::from("c"), PathBuf::from("d")]));
+}
+
+#[test]
+fn environment_var_injected() {
+    #[derive(Kurisu)]
+    #[kurisu(implicit_env)]
+    struct Yargs {
+        #[kurisu(env = "INJECTED_DB_HOST")]
+        host: String,
+        path: String,
+        home: String,
+    }
+
+    let mut env: HashMap<String, String> = HashMap::new();
+    env.insert(String::from("INJECTED_DB_HOST"), String::from("db.example.com"));
+    env.insert(String::from("PATH"), String::from("/usr/bin"));
+
+    let yargs = Yargs::from_args_env(vec_to_string(vec![]), env);
+    assert_eq!(yargs.host, "db.example.com");
+    assert_eq!(yargs.path, "/usr/bin");
+    assert_eq!(yargs.home, "");
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    assert_eq!(info.env.get("PATH"), Some("/usr/bin"));
+    assert_eq!(info.env.get("path"), None);
+    assert_eq!(info.env.get_ignore_case("path"), Some("/usr/bin"));
+}
+
+#[test]
+fn environment_snapshot() {
+    let mut vars: HashMap<String, String> = HashMap::new();
+    vars.insert(String::from("Foo"), String::from("mixed"));
+    vars.insert(String::from("FOO"), String::from("upper"));
+    vars.insert(String::from("DB_PASSWORD"), String::from("hunter2"));
+    let env = Env::from(vars);
+
+    assert_eq!(env.get_ignore_case("Foo"), Some("mixed"));
+    assert_eq!(env.get_ignore_case("foo"), Some("upper"));
+    assert_eq!(env.get_ignore_case("db_password"), Some("hunter2"));
+    assert_eq!(format!("{:?}", env), "Env { names: [\"DB_PASSWORD\", \"FOO\", \"Foo\"], .. }");
+}
+
+#[test]
+fn environment_var_precedence() {
+    std::env::set_var("PRECEDENCE_DB_HOST", "db.example.com");
+    std::env::set_var("PRECEDENCE_DB_PORT", "5432");
+
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(env = "PRECEDENCE_DB_HOST", default = "localhost")]
+        host: String,
+        #[kurisu(env = "PRECEDENCE_DB_PORT", default = "3306")]
+        port: usize,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--port=1234"]));
+    assert_eq!(yargs.host, "db.example.com");
+    assert_eq!(yargs.port, 1234);
+}
+
+#[test]
+fn environment_var_precedence_override() {
+    std::env::set_var("PRECEDENCE_OVERRIDE_DB_HOST", "db.example.com");
+    std::env::set_var("PRECEDENCE_OVERRIDE_DB_USER", "admin");
+
+    #[derive(Kurisu)]
+    #[kurisu(precedence = "cli,default,env")]
+    struct Yargs {
+        #[kurisu(env = "PRECEDENCE_OVERRIDE_DB_HOST", default = "localhost")]
+        host: String,
+        #[kurisu(env = "PRECEDENCE_OVERRIDE_DB_USER")]
+        user: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![]));
+    assert_eq!(yargs.host, "localhost");
+    assert_eq!(yargs.user, "admin");
+}
+
 #[test]
 fn annotation_parser() {
     #[derive(Kurisu)]
@@ -562,3 +797,639 @@ fn annotation_parser() {
     assert_eq!(yargs.test, String::from("HELLO"));
     assert_eq!(yargs.hello, String::from(""));
 }
+
+#[test]
+fn annotation_parser_value() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short, parse_with = "parse_percent")]
+        quality: u8,
+        #[kurisu(parse_with = "parse_percent")]
+        steps: Vec<u8>,
+        #[kurisu(parse_with = "parse_percent")]
+        missing: u8,
+    }
+
+    fn parse_percent(value: &str) -> Result<u8, String> {
+        match value.trim_end_matches('%').parse::<u8>() {
+            Ok(percent) if percent <= 100 => Ok(percent),
+            _ => Err(String::from("expected a percentage up to 100%")),
+        }
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-q", "80%", "--steps=10,50%", "--steps", "100"]));
+    assert_eq!(yargs.quality, 80);
+    assert_eq!(yargs.steps, vec![10, 50, 100]);
+    assert_eq!(yargs.missing, 0);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn annotation_parser_value_collections() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(parse_with = "parse_percent")]
+        levels: BTreeSet<u8>,
+        #[kurisu(parse_with = "parse_percent")]
+        queue: VecDeque<u8>,
+        #[kurisu(parse_with = "parse_percent")]
+        rgb: [u8; 3],
+    }
+
+    fn parse_percent(value: &str) -> Result<u8, String> {
+        value.trim_end_matches('%').parse::<u8>().map_err(|e| e.to_string())
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![
+        "--levels=50%,10,50",
+        "--queue",
+        "3",
+        "--queue=1%",
+        "--rgb",
+        "100%,50%,0",
+    ]));
+    assert_eq!(yargs.levels.iter().copied().collect::<Vec<u8>>(), vec![10, 50]);
+    assert_eq!(yargs.queue, VecDeque::from(vec![3, 1]));
+    assert_eq!(yargs.rgb, [100, 50, 0]);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn annotation_parser_value_array_arity() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(parse_with = "parse_level")]
+        rgb: [u8; 3],
+    }
+
+    fn parse_level(value: &str) -> Result<u8, String> {
+        value.parse::<u8>().map_err(|e| e.to_string())
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--rgb", "1,2"]));
+    let error = kurisu::validate_usage(&yargs).unwrap();
+    assert_eq!(error.to_string(), "--rgb <RGB>... has an invalid value \"1 2\": expected 3 values, got 2");
+}
+
+#[test]
+fn annotation_parser_value_invalid() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(parse_with = "parse_ratio")]
+        ratios: Vec<f64>,
+    }
+
+    fn parse_ratio(value: &str) -> Result<f64, String> {
+        let (a, b) = value.split_once(':').ok_or("expected a ratio like 16:9")?;
+        let a = a.parse::<f64>().map_err(|e| e.to_string())?;
+        let b = b.parse::<f64>().map_err(|e| e.to_string())?;
+        Ok(a / b)
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--ratios", "4:3,wide"]));
+    assert_eq!(yargs.ratios.len(), 1);
+    let error = kurisu::validate_usage(&yargs).unwrap();
+    assert_eq!(
+        error.to_string(),
+        "--ratios <RATIOS>... has an invalid value \"wide\": expected a ratio like 16:9"
+    );
+}
+
+#[test]
+fn annotation_skip() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        name: String,
+        #[kurisu(skip)]
+        config: PathBuf,
+        #[kurisu(skip, default = "/etc/yargs")]
+        config_dir: PathBuf,
+        #[kurisu(skip, default_expr = "std::env::temp_dir()")]
+        cache_dir: PathBuf,
+        #[kurisu(skip_with = "resolve_retries")]
+        retries: usize,
+        _cache: Vec<String>,
+    }
+
+    fn resolve_retries() -> usize {
+        3
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--name", "kurisu"]));
+    assert_eq!(yargs.name, String::from("kurisu"));
+    assert_eq!(yargs.config, PathBuf::default());
+    assert_eq!(yargs.config_dir, PathBuf::from("/etc/yargs"));
+    assert_eq!(yargs.cache_dir, std::env::temp_dir());
+    assert_eq!(yargs.retries, 3);
+    assert!(yargs._cache.is_empty());
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    assert_eq!(3, info.args.len());
+    assert!(info
+        .args
+        .iter()
+        .all(|a| !["config", "config_dir", "cache_dir", "retries", "_cache"].contains(&a.name)));
+}
+
+#[test]
+fn positional_last() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos)]
+        sources: Vec<PathBuf>,
+        #[kurisu(pos = "last")]
+        dest: PathBuf,
+        verbose: bool,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["a.txt", "b.txt", "dir", "--verbose"]));
+    assert_eq!(yargs.sources, vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")]);
+    assert_eq!(yargs.dest, PathBuf::from("dir"));
+    assert!(yargs.verbose);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    let arg = info.args.iter().find(|a| a.name == "dest");
+    assert_eq!(arg.unwrap().position, Some(-1));
+}
+
+#[test]
+fn trailing() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos = 1)]
+        bin: String,
+        release: bool,
+        #[kurisu(trailing)]
+        rest: Vec<String>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["kurisu", "--release", "--", "--nope", "-x", "value", "--", "end"]));
+    assert_eq!(yargs.bin, String::from("kurisu"));
+    assert!(yargs.release);
+    assert_eq!(yargs.rest, vec_to_string(vec!["--nope", "-x", "value", "--", "end"]));
+    assert!(kurisu::validate_usage(&yargs).is_none());
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    let arg = info.get_trailing_arg();
+    assert!(arg.is_some());
+    assert_eq!(arg.unwrap().short, None);
+    assert_eq!(arg.unwrap().long, None);
+    assert_eq!(arg.unwrap().occurrences, 5);
+}
+
+#[test]
+fn trailing_empty() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        release: bool,
+        #[kurisu(trailing)]
+        rest: Vec<String>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--release"]));
+    assert!(yargs.release);
+    assert!(yargs.rest.is_empty());
+}
+
+#[test]
+fn unknown() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short)]
+        verbose: bool,
+        #[kurisu(short)]
+        all: bool,
+        #[kurisu(short)]
+        long_listing: bool,
+        #[kurisu(pos)]
+        files: Vec<String>,
+        #[kurisu(unknown)]
+        extra: Vec<String>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![
+        "--color=always",
+        "-va",
+        "file1.txt",
+        "-Xmx",
+        "--depth",
+        "2",
+        "-lq",
+        "--",
+        "--not-unknown",
+    ]));
+
+    assert!(yargs.verbose);
+    assert!(yargs.all);
+    assert!(yargs.long_listing);
+    assert_eq!(yargs.extra, vec_to_string(vec!["--color=always", "-Xmx", "--depth", "-q"]));
+    assert_eq!(yargs.files, vec_to_string(vec!["file1.txt", "2", "--not-unknown"]));
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn annotation_unknown_negative_number() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos = 1)]
+        n: isize,
+        #[kurisu(unknown)]
+        extra: Vec<String>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-5", "-x"]));
+    assert_eq!(yargs.n, -5);
+    assert_eq!(yargs.extra, vec_to_string(vec!["-x"]));
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn numeric_shorthand() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short = "C", numeric_shorthand)]
+        context: usize,
+        offset: isize,
+        ratio: f64,
+        #[kurisu(short)]
+        invert: bool,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-12", "--offset", "-3", "--ratio", "-1", "-i"]));
+    assert_eq!(yargs.context, 12);
+    assert_eq!(yargs.offset, -3);
+    assert!(approx_eq!(f64, yargs.ratio, -1.0));
+    assert!(yargs.invert);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    let env_args = normalize_env_args(&info.env_args, &info.args);
+    assert_eq!(env_args, vec_to_string(vec!["--context=12", "--offset=-3", "--ratio=-1", "-i"]));
+}
+
+#[test]
+fn default_missing() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short, vname = "when", default_missing = "always")]
+        color: String,
+        #[kurisu(pos = 1)]
+        file: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--color", "test.txt"]));
+    assert_eq!(yargs.color, "always");
+    assert_eq!(yargs.file, "test.txt");
+    assert!(kurisu::validate_usage(&yargs).is_none());
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    let arg = info.args.iter().find(|a| a.name == "color").unwrap();
+    assert_eq!(arg.to_string().trim(), "-c --color[=<WHEN>]");
+}
+
+#[test]
+fn default_missing_value() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short, default_missing = "always")]
+        color: String,
+        #[kurisu(pos = 1)]
+        file: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["test.txt", "--color=never"]));
+    assert_eq!(yargs.color, "never");
+    assert_eq!(yargs.file, "test.txt");
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn num_values() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short, vname = "old,new", num_values = 2)]
+        rename: (String, String),
+        #[kurisu(num_values = 2)]
+        point: Vec<isize>,
+        #[kurisu(pos)]
+        files: Vec<PathBuf>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![
+        "-r",
+        "a.txt",
+        "b.txt",
+        "--point=10",
+        "-20",
+        "--point",
+        "3",
+        "4",
+        "c.txt",
+    ]));
+    assert_eq!(yargs.rename, (String::from("a.txt"), String::from("b.txt")));
+    assert_eq!(yargs.point, vec![10, -20, 3, 4]);
+    assert_eq!(yargs.files, vec![PathBuf::from("c.txt")]);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    let arg = info.args.iter().find(|a| a.name == "rename").unwrap();
+    assert_eq!(arg.to_string().trim(), "-r --rename <OLD> <NEW>");
+    let arg = info.args.iter().find(|a| a.name == "point").unwrap();
+    assert_eq!(arg.to_string().trim(), "--point <POINT> <POINT>...");
+}
+
+#[test]
+fn allow_hyphen_values() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short, allow_hyphen_values)]
+        pattern: String,
+        #[kurisu(allow_hyphen_values)]
+        args: Vec<String>,
+        #[kurisu(allow_hyphen_values)]
+        ratio: f64,
+        #[kurisu(short)]
+        invert: bool,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![
+        "-p",
+        "-foo",
+        "--args",
+        "-Xmx2g",
+        "--args",
+        "--verbose",
+        "--ratio",
+        "-1.5e3",
+        "-i",
+    ]));
+    assert_eq!(yargs.pattern, "-foo");
+    assert_eq!(yargs.args, vec_to_string(vec!["-Xmx2g", "--verbose"]));
+    assert!(approx_eq!(f64, yargs.ratio, -1500.0));
+    assert!(yargs.invert);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn allow_hyphen_values_struct() {
+    #[derive(Kurisu)]
+    #[kurisu(allow_hyphen_values)]
+    struct Yargs {
+        pattern: String,
+        #[kurisu(num_values = 2)]
+        range: (isize, String),
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--range", "-3", "-end", "--pattern", "--", "--pattern"]));
+    assert_eq!(yargs.range, (-3, String::from("-end")));
+    assert_eq!(yargs.pattern, "");
+}
+
+#[test]
+fn delimiter() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(delimiter = ":")]
+        include: Vec<PathBuf>,
+        exclude: Vec<String>,
+        #[kurisu(no_delimiter)]
+        pattern: Vec<String>,
+        #[kurisu(short)]
+        margins: Vec<isize>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![
+        "--include=/usr/lib:/lib",
+        "--exclude",
+        "a\\,b.txt,c.txt",
+        "--pattern",
+        "x,y",
+        "-m",
+        "-4,-5",
+    ]));
+    assert_eq!(yargs.include, vec![PathBuf::from("/usr/lib"), PathBuf::from("/lib")]);
+    assert_eq!(yargs.exclude, vec_to_string(vec!["a,b.txt", "c.txt"]));
+    assert_eq!(yargs.pattern, vec_to_string(vec!["x,y"]));
+    assert_eq!(yargs.margins, vec![-4, -5]);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn positional_many() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos = 1)]
+        command: String,
+        #[kurisu(pos)]
+        paths: Vec<PathBuf>,
+        #[kurisu(pos = "last")]
+        dest: PathBuf,
+    }
+
+    let mut env_args = vec![String::from("cp")];
+    env_args.extend((0..1000).map(|i| format!("file_{}.txt", i)));
+    env_args.push(String::from("/tmp"));
+
+    let yargs = Yargs::from_args(env_args);
+    assert_eq!(yargs.command, "cp");
+    assert_eq!(yargs.paths.len(), 1000);
+    assert_eq!(yargs.paths[999], PathBuf::from("file_999.txt"));
+    assert_eq!(yargs.dest, PathBuf::from("/tmp"));
+}
+
+#[test]
+fn repeated_single_value() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short)]
+        verbose: bool,
+        color: String,
+        #[kurisu(repeat = "first")]
+        level: usize,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-v", "-v", "--color=auto", "--color=never", "--level=1", "--level=2"]));
+    assert!(yargs.verbose);
+    assert_eq!(yargs.color, String::from("never"));
+    assert_eq!(yargs.level, 1);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn repeated_single_value_error() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(repeat = "error")]
+        color: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--color=auto", "--color=never"]));
+    let error = kurisu::validate_usage(&yargs).unwrap();
+    assert_eq!(error.to_string(), "option --color <COLOR> cannot be used multiple times");
+}
+
+#[test]
+fn collections() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short)]
+        tags: HashSet<String>,
+        #[kurisu(short)]
+        levels: BTreeSet<usize>,
+        #[kurisu(short)]
+        queue: VecDeque<PathBuf>,
+        #[kurisu(short)]
+        rgb: [u8; 3],
+        #[kurisu(default = "1,2")]
+        origin: [isize; 2],
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec![
+        "-t=a",
+        "-t",
+        "b,a",
+        "-l",
+        "3,1,3",
+        "-q",
+        "a.txt",
+        "-q=b.txt",
+        "--rgb",
+        "255,128,0",
+    ]));
+    assert_eq!(yargs.tags, vec_to_string(vec!["a", "b"]).into_iter().collect::<HashSet<String>>());
+    assert_eq!(yargs.levels.iter().copied().collect::<Vec<usize>>(), vec![1, 3]);
+    assert_eq!(yargs.queue, VecDeque::from(vec![PathBuf::from("a.txt"), PathBuf::from("b.txt")]));
+    assert_eq!(yargs.rgb, [255, 128, 0]);
+    assert_eq!(yargs.origin, [1, 2]);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+    Yargs::assert_schema();
+}
+
+#[test]
+fn collection_alias() {
+    std::env::set_var("ALIAS_SEARCH", "c:d");
+
+    type Paths = Vec<PathBuf>;
+
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short)]
+        inc: Paths,
+        #[kurisu(env = "ALIAS_SEARCH")]
+        search: Paths,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-i", "a", "-i", "b"]));
+    assert_eq!(yargs.inc, vec![PathBuf::from("a"), PathBuf::from("b")]);
+    assert_eq!(yargs.search, vec![PathBuf::from("c"), PathBuf::from("d")]);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn tuple_single_value() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        size: (u8, u8),
+        offset: (isize, isize, isize),
+        #[kurisu(pos = 1)]
+        range: (usize, usize),
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--size", "80x60", "--offset=1,2,3", "10,20"]));
+    assert_eq!(yargs.size, (80, 60));
+    assert_eq!(yargs.offset, (1, 2, 3));
+    assert_eq!(yargs.range, (10, 20));
+}
+
+#[test]
+fn array_wrong_arity() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        rgb: [u8; 3],
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--rgb", "1,2"]));
+    let error = kurisu::validate_usage(&yargs).unwrap();
+    assert_eq!(error.to_string(), "--rgb <RGB>... has an invalid value \"1 2\": expected 3 values, got 2");
+}
+
+#[test]
+fn tuple_text_not_split() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        pair: (String, String),
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--pair", "box"]));
+    let error = kurisu::validate_usage(&yargs).unwrap();
+    assert_eq!(error.to_string(), "--pair <PAIR> has an invalid value \"box\": expected 2 values, got 1");
+}
+
+#[derive(Debug, PartialEq)]
+enum LogLevel {
+    Info,
+    Debug,
+}
+
+// `#[default]` on enum variants requires Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for LogLevel {
+    fn default() -> Self {
+        LogLevel::Info
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            _ => Err(String::from("expected info or debug")),
+        }
+    }
+}
+
+#[test]
+fn from_str() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short, from_str)]
+        level: LogLevel,
+        #[kurisu(from_str)]
+        quiet: LogLevel,
+        #[kurisu(from_str, default = "8080")]
+        port: u16,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-l", "debug"]));
+    assert_eq!(yargs.level, LogLevel::Debug);
+    assert_eq!(yargs.quiet, LogLevel::Info);
+    assert_eq!(yargs.port, 8080);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+}
+
+#[test]
+fn from_str_invalid() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short, from_str)]
+        level: LogLevel,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-l", "loud"]));
+    assert_eq!(yargs.level, LogLevel::Info);
+    let error = kurisu::validate_usage(&yargs).unwrap();
+    assert_eq!(
+        error.to_string(),
+        "-l --level <LEVEL> has an invalid value \"loud\": expected info or debug"
+    );
+}
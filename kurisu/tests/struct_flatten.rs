@@ -0,0 +1,103 @@
+use kurisu::*;
+
+fn vec_to_string(args: Vec<&str>) -> Vec<String> {
+    let mut strings = Vec::new();
+    for arg in args {
+        strings.push(arg.to_string());
+    }
+
+    strings
+}
+
+#[derive(Kurisu)]
+struct DbArgs {
+    #[kurisu(short = "H")]
+    db_host: String,
+    #[kurisu(default = "3306")]
+    db_port: usize,
+}
+
+#[derive(Kurisu)]
+struct LogArgs {
+    #[kurisu(short = "v", nolong)]
+    verbose: u8,
+}
+
+#[test]
+fn flatten() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short)]
+        name: String,
+        #[kurisu(flatten)]
+        db: DbArgs,
+        #[kurisu(flatten)]
+        log: LogArgs,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-n", "kurisu", "-H", "localhost", "-vvv"]));
+    assert_eq!(yargs.name, String::from("kurisu"));
+    assert_eq!(yargs.db.db_host, String::from("localhost"));
+    assert_eq!(yargs.db.db_port, 3306);
+    assert_eq!(yargs.log.verbose, 3);
+    assert!(kurisu::validate_usage(&yargs).is_none());
+
+    let info = Yargs::get_info_instance(Vec::new()).lock().unwrap();
+    assert_eq!(6, info.args.len());
+    let arg = info.args.iter().find(|a| a.name == "db_port");
+    assert!(arg.is_some());
+    assert_eq!(arg.unwrap().long, Some("db-port"));
+    assert_eq!(arg.unwrap().value, vec![String::from("3306")]);
+}
+
+#[test]
+fn flatten_nested() {
+    #[derive(Kurisu)]
+    struct ServiceArgs {
+        #[kurisu(flatten)]
+        db: DbArgs,
+        workers: usize,
+    }
+
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(flatten)]
+        service: ServiceArgs,
+        dry_run: bool,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--db-port=5432", "--workers", "4", "--dry-run"]));
+    assert_eq!(yargs.service.db.db_port, 5432);
+    assert_eq!(yargs.service.workers, 4);
+    assert!(yargs.dry_run);
+}
+
+#[test]
+#[should_panic(expected = "short flag -H of `db_host` already bound to `host`")]
+fn flatten_short_collision() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short = "H")]
+        host: String,
+        #[kurisu(flatten)]
+        db: DbArgs,
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "long flag --db-port of `db_port` already bound to `port`")]
+fn flatten_long_collision() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(long = "db-port")]
+        port: usize,
+        #[kurisu(flatten)]
+        db: DbArgs,
+    }
+
+    Yargs::assert_schema();
+}
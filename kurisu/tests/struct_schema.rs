@@ -0,0 +1,145 @@
+use kurisu::*;
+use std::path::PathBuf;
+
+#[allow(dead_code)]
+#[derive(Kurisu)]
+struct DbArgs {
+    db_host: String,
+    #[kurisu(default = "3306")]
+    db_port: usize,
+}
+
+#[test]
+fn valid_schema() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos = 1)]
+        source: PathBuf,
+        #[kurisu(pos = 2)]
+        target: PathBuf,
+        #[kurisu(default = "1.5")]
+        ratio: f64,
+        #[kurisu(default = "true")]
+        enabled: bool,
+        #[kurisu(required_if = "db_host")]
+        password: String,
+        #[kurisu(flatten)]
+        db: DbArgs,
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "default \"fourty two\" of `knots` is not a valid usize")]
+fn invalid_default() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(default = "fourty two")]
+        knots: usize,
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "default_missing \"all\" of `depth` is not a valid usize")]
+fn invalid_default_missing() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(default_missing = "all")]
+        depth: usize,
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "default \"-1\" of `port` is not a valid u16: invalid digit found in string")]
+fn invalid_default_from_str() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(from_str, default = "-1")]
+        port: u16,
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "default \"many\" of `retries` is not a valid usize")]
+fn invalid_default_skip() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(skip, default = "many")]
+        retries: usize,
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "default \"-1\" of `db_port` is not a valid usize")]
+fn invalid_default_flatten() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct PortArgs {
+        #[kurisu(default = "-1")]
+        db_port: usize,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(flatten)]
+        port: PortArgs,
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "required_if of `password` references unknown field `db_user`")]
+fn invalid_required_if_flatten() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(required_if = "db_user")]
+        password: String,
+        #[kurisu(flatten)]
+        db: DbArgs,
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "default \"800\" of `size` is not a valid (String, String): expected 2 values, got 1")]
+fn invalid_default_tuple() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(default = "800")]
+        size: (String, String),
+    }
+
+    Yargs::assert_schema();
+}
+
+#[test]
+#[should_panic(expected = "short flag -h of `host` already bound to `usage`")]
+fn builtin_collision() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short = "o", aliases = "h")]
+        host: String,
+    }
+
+    Yargs::assert_schema();
+}
@@ -0,0 +1,19 @@
+use kurisu::*;
+
+fn parse_port(value: &str) -> Result<usize, std::num::ParseIntError> {
+    value.parse()
+}
+
+#[derive(Kurisu)]
+struct Yargs {
+    #[kurisu(default_expr = "String::from(\"localhost\")")]
+    host: String,
+    #[kurisu(skip, default = "8080", default_expr = "8080")]
+    port: usize,
+    #[kurisu(from_str, parse_with = "parse_port")]
+    timeout: usize,
+    #[kurisu(from_str)]
+    _retries: usize,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use kurisu::*;
+
+#[derive(Kurisu)]
+#[kurisu(name)]
+struct Yargs {
+    #[kurisu(pos = "1")]
+    file: String,
+    #[kurisu(short = "ab")]
+    bobby: String,
+    #[kurisu(nolong = "yes")]
+    knots: usize,
+}
+
+fn main() {}
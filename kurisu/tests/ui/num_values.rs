@@ -0,0 +1,13 @@
+use kurisu::*;
+
+#[derive(Kurisu)]
+struct Yargs {
+    #[kurisu(num_values = 3)]
+    rename: (String, String),
+    #[kurisu(num_values = 0)]
+    size: Vec<usize>,
+    #[kurisu(num_values = 2)]
+    dims: [usize; 3],
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use kurisu::*;
+
+#[derive(Kurisu)]
+struct Yargs {
+    #[kurisu(num_values = 2)]
+    point: String,
+}
+
+fn main() {}
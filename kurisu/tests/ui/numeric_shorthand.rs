@@ -0,0 +1,22 @@
+use kurisu::*;
+
+#[derive(Kurisu)]
+struct Yargs {
+    #[kurisu(pos = 1, numeric_shorthand)]
+    context: usize,
+}
+
+#[derive(Kurisu)]
+struct Zargs {
+    #[kurisu(nolong, numeric_shorthand)]
+    context: usize,
+}
+
+#[derive(Kurisu)]
+#[kurisu(auto_shorts)]
+struct Shorts {
+    #[kurisu(nolong, numeric_shorthand)]
+    context: usize,
+}
+
+fn main() {}
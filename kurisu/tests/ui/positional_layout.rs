@@ -0,0 +1,13 @@
+use kurisu::*;
+
+#[derive(Kurisu)]
+struct Yargs {
+    #[kurisu(pos = 1)]
+    source: String,
+    #[kurisu(pos = 1)]
+    target: String,
+    #[kurisu(pos = 3)]
+    mode: String,
+}
+
+fn main() {}
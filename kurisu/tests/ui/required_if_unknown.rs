@@ -0,0 +1,10 @@
+use kurisu::*;
+
+#[derive(Kurisu)]
+struct Yargs {
+    user: String,
+    #[kurisu(required_if = "usr")]
+    password: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use kurisu::*;
+
+#[derive(Kurisu)]
+struct Yargs {
+    #[kurisu(numeric_shorthand)]
+    context: String,
+    #[kurisu(trailing)]
+    rest: Vec<String>,
+    #[kurisu(trailing)]
+    others: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use kurisu::*;
+
+#[derive(Kurisu)]
+#[kurisu(nosrot)]
+struct Yargs {
+    #[kurisu(short, parser = "parse_bobby")]
+    bobby: String,
+    #[kurisu(defualt = "42")]
+    knots: usize,
+}
+
+fn main() {}
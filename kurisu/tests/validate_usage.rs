@@ -141,7 +141,7 @@ fn aliases() {
     let error = kurisu::validate_usage(&yargs);
     assert_eq!(error, None);
     assert!(yargs.aliases);
-    assert!(!yargs.multiple); // TODO: Test aliases, shouldn't this be true?
+    assert!(yargs.multiple);
 }
 
 #[test]
@@ -328,3 +328,160 @@ fn positional_missing_value() {
     assert_eq!(yargs.my_file, PathBuf::default());
     assert_eq!(yargs.operation, String::from("delete"));
 }
+
+#[test]
+fn options_ended() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        long: String,
+        #[kurisu(pos)]
+        files: Vec<String>,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--long", "test", "--", "-a", "--file"]));
+    let error = kurisu::validate_usage(&yargs);
+    assert_eq!(error, None);
+    assert_eq!(yargs.long, String::from("test"));
+    assert_eq!(yargs.files, vec_to_string(vec!["-a", "--file"]));
+}
+
+#[test]
+fn invalid_arg_before_last_pos() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos = 1)]
+        source: String,
+        #[kurisu(pos = "last")]
+        target: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["file1.txt", "file2.txt", "file3.txt"]));
+    let error = kurisu::validate_usage(&yargs);
+    assert_eq!(error.unwrap(), Error::Invalid(String::from("file2.txt")));
+    assert_eq!(yargs.source, String::from("file1.txt"));
+    assert_eq!(yargs.target, String::from("file3.txt"));
+}
+
+#[test]
+fn missing_last_pos() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos = 1)]
+        source: String,
+        #[kurisu(pos = "last")]
+        dest: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["only"]));
+    assert_eq!(yargs.source, String::from("only"));
+    assert_eq!(yargs.dest, String::from(""));
+    let error = kurisu::validate_usage(&yargs);
+    assert_eq!(error.unwrap().to_string(), "missing argument <DEST>");
+}
+
+#[test]
+fn missing_last_pos_with_infinite() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(pos = 1)]
+        command: String,
+        #[kurisu(pos)]
+        sources: Vec<String>,
+        #[kurisu(pos = "last")]
+        dest: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["cp"]));
+    assert_eq!(yargs.command, String::from("cp"));
+    assert!(yargs.sources.is_empty());
+    assert_eq!(yargs.dest, String::from(""));
+    let error = kurisu::validate_usage(&yargs);
+    assert_eq!(error.unwrap().to_string(), "missing argument <DEST>");
+}
+
+#[test]
+fn allow_unknown() {
+    #[derive(Kurisu)]
+    #[kurisu(allow_unknown)]
+    struct Yargs {
+        #[kurisu(short)]
+        short: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["-s", "test1", "--test", "-k"]));
+    let error = kurisu::validate_usage(&yargs);
+    assert_eq!(error, None);
+    assert_eq!(yargs.short, String::from("test1"));
+}
+
+#[test]
+fn invalid_long_prefix() {
+    #[derive(Kurisu)]
+    struct Yargs {
+        verbose: bool,
+        color: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--colorize=always", "--verbose-mode"]));
+    let error = kurisu::validate_usage(&yargs);
+    assert_eq!(error.unwrap(), Error::Invalid(String::from("--colorize=always")));
+    assert!(!yargs.verbose);
+    assert_eq!(yargs.color, String::default());
+}
+
+#[test]
+fn abbreviations() {
+    #[derive(Kurisu)]
+    #[kurisu(allow_abbrev)]
+    struct Yargs {
+        verbose: bool,
+        #[kurisu(aliases = "colour")]
+        color: String,
+        colormap: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--verb", "--colou=always", "--colorm", "viridis"]));
+    let error = kurisu::validate_usage(&yargs);
+    assert_eq!(error, None);
+    assert!(yargs.verbose);
+    assert_eq!(yargs.color, String::from("always"));
+    assert_eq!(yargs.colormap, String::from("viridis"));
+}
+
+#[test]
+fn abbreviations_ambiguous() {
+    #[derive(Kurisu)]
+    #[kurisu(allow_abbrev)]
+    struct Yargs {
+        color: String,
+        colormap: String,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--col=always"]));
+    let error = kurisu::validate_usage(&yargs);
+    assert_eq!(
+        error.unwrap(),
+        Error::Ambiguous(String::from("--col=always"), vec_to_string(vec!["--color", "--colormap"]))
+    );
+    assert_eq!(yargs.color, String::default());
+    assert_eq!(yargs.colormap, String::default());
+}
+
+#[test]
+fn num_values_missing() {
+    #[allow(dead_code)]
+    #[derive(Kurisu)]
+    struct Yargs {
+        #[kurisu(short, num_values = 2)]
+        rename: (String, String),
+        #[kurisu(short)]
+        verbose: bool,
+    }
+
+    let yargs = Yargs::from_args(vec_to_string(vec!["--rename", "old.txt", "-v"]));
+    let error = kurisu::validate_usage(&yargs);
+    assert!(matches!(error.unwrap(), Error::RequiresValue(arg) if arg.name == "rename"));
+    assert!(yargs.verbose);
+}